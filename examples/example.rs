@@ -3,7 +3,7 @@ use discord_ipc_rp::{Result, activity, DiscordIpc, DiscordIpcClient};
 fn main() -> Result<()> {
     env_logger::Builder::new().filter_level(log::LevelFilter::Debug).init();
 
-    let mut client = DiscordIpcClient::new("771124766517755954");
+    let mut client = DiscordIpcClient::new("771124766517755954")?;
     client.connect()?;
 
     let activity = activity::Activity::new()
@@ -0,0 +1,68 @@
+//! Provides a helper delivering the updates of the user's achievements
+//! to a callback.
+use crate::{events::Event, models::UserAchievement, subscription::Subscription, ActivityEvent, DiscordIpc, Result};
+
+/// Delivers [`UserAchievementUpdate`](ActivityEvent::UserAchievementUpdate)
+/// events to a callback.
+///
+/// # Examples
+/// ```no_run
+/// # use discord_ipc_rp::{achievements::AchievementWatcher, models::{Snowflake, UserAchievement}, DiscordIpc, DiscordIpcClient};
+/// # fn show_unlocked(_: Snowflake) {}
+/// # fn main() -> discord_ipc_rp::Result<()> {
+/// # let mut client = DiscordIpcClient::new("771124766517755954")?;
+/// let mut watcher = AchievementWatcher::subscribe(&mut client, |achievement: &UserAchievement| {
+///     if achievement.percent_complete == 100 {
+///         show_unlocked(achievement.achievement_id);
+///     }
+/// })?;
+/// loop {
+///     let event = client.recv_event()?;
+///     watcher.handle_event(&event);
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct AchievementWatcher<F: FnMut(&UserAchievement)> {
+    callback: F,
+    subscription: Subscription,
+}
+
+impl<F: FnMut(&UserAchievement)> AchievementWatcher<F> {
+    /// Creates a new `AchievementWatcher` calling `callback`, without
+    /// subscribing to any events.
+    pub fn new(callback: F) -> Self {
+        Self { callback, subscription: Subscription::new(ActivityEvent::UserAchievementUpdate) }
+    }
+
+    /// Creates a new `AchievementWatcher` calling `callback`, subscribing
+    /// to the updates of the user's achievements.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if subscribing failed.
+    pub fn subscribe<C: DiscordIpc>(client: &mut C, callback: F) -> Result<Self> {
+        let subscription = Subscription::subscribe(client, ActivityEvent::UserAchievementUpdate)?;
+
+        Ok(Self { callback, subscription })
+    }
+
+    /// Unsubscribes from the updates of the user's achievements, if the
+    /// watcher was created with [`subscribe`](Self::subscribe).
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if unsubscribing failed.
+    pub fn unsubscribe<C: DiscordIpc>(self, client: &mut C) -> Result<()> {
+        self.subscription.unsubscribe(client)
+    }
+
+    /// Calls the callback if the event is an achievement update, and returns
+    /// whether it was called.
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        let Event::UserAchievementUpdate(achievement) = event else {
+            return false;
+        };
+        (self.callback)(achievement);
+
+        true
+    }
+}
@@ -1,10 +1,25 @@
 use crate::{
+    Error,
     Result,
+    ClientState,
+    events::Event,
+    oauth2::{TokenExchanger, TokenStore},
     activity::Activity,
+    models::{
+        AcceptActivityInviteArgs, AchievementProgress, SetUserAchievementArgs, UserAchievement, ActivityActionType, ActivityInvite, ActivityInviteUserArgs, UserArgs, AuthenticateArgs,
+        OpenOverlayActivityInviteArgs, OpenOverlayGuildInviteArgs, OverlayArgs, OverlayState, SetOverlayLockedArgs,
+        GetUserArgs, Relationship, RelationshipsResponse, User,
+        BrowserHandoffArgs, CodeArgs, DeepLink, GetImageArgs, GetImageResponse, Image, ImageFormat,
+        AuthenticateResponse, AuthorizeArgs, AuthorizeResponse, CaptureShortcutAction, CaptureShortcutArgs, Channel, ChannelArgs, ChannelsResponse, Guild,
+        ConnectToLobbyArgs, Lobby, LobbyArgs, LobbyEventArgs, LobbyOptions, LobbySearchQuery, SendToLobbyArgs, UpdateLobbyArgs, UpdateLobbyMemberArgs,
+        Entitlement, SelectChannelArgs, SignedTicket, Sku, StartPurchaseArgs, TicketResponse,
+        GuildArgs, GuildsResponse, Invite, Scope, Snowflake, StoredToken, Token, UserVoiceSettings, VoiceSettings,
+    },
     pack_unpack::{pack, unpack},
 };
-use serde::{Deserializer, de::IntoDeserializer, Serialize, Deserialize};
+use serde::{Deserializer, de::{DeserializeOwned, IgnoredAny, IntoDeserializer}, Serialize, Deserialize};
 use serde_json::Value;
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
 use strum::FromRepr;
 use uuid::Uuid;
 
@@ -12,19 +27,20 @@ use uuid::Uuid;
 #[derive(Serialize, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub struct HandshakeData {
     v: u32,
-    client_id: String,
+    client_id: Snowflake,
 }
 impl HandshakeData {
-    fn new<S: Into<String>>(client_id: S) -> Self {
+    fn new(client_id: Snowflake) -> Self {
         Self {
             v: 1,
-            client_id: client_id.into(),
+            client_id,
         }
     }
 }
 
+/// The arguments of a [`SetActivity`](ActivityCmd::SetActivity) command.
 #[derive(Serialize, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
-struct ActivityArgs<'a> {
+pub struct ActivityArgs<'a> {
     pid: u32,
     activity: Option<Activity<'a>>,
 }
@@ -145,49 +161,59 @@ pub enum ActivityEvent {
 
 /// This defines a message sent from your app to Discord.
 #[derive(Serialize, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
-pub struct DiscordIPCCommandOutgoing<'a> {
+pub struct DiscordIPCCommandOutgoing<A> {
     /// The command ID of this request.
     cmd: ActivityCmd,
-    /// The unique ID of this request, a response will be sent with the matching ID.
-    args: ActivityArgs<'a>,
     /// The arguments of this request.
+    args: A,
+    /// The unique ID of this request, a response will be sent with the matching ID.
     nonce: String,
     /// Events with `cmd` `SUBSCRIBE` will have an `event` parameter defining the name of the event (e.g. [MessageCreate](ActivityEvent::MessageCreate)).
+    #[serde(skip_serializing_if = "Option::is_none")]
     evt: Option<ActivityEvent>,
 }
-impl<'a> DiscordIPCCommandOutgoing<'a> {
-    fn set_activity(activity: Activity<'a>) -> Self {
+impl<A> DiscordIPCCommandOutgoing<A> {
+    fn new(cmd: ActivityCmd, args: A, evt: Option<ActivityEvent>) -> Self {
         Self {
-            cmd: ActivityCmd::SetActivity,
-            args: ActivityArgs {
+            cmd,
+            args,
+            nonce: Uuid::new_v4().to_string(),
+            evt,
+        }
+    }
+}
+impl<'a> DiscordIPCCommandOutgoing<ActivityArgs<'a>> {
+    fn set_activity(activity: Activity<'a>) -> Self {
+        Self::new(
+            ActivityCmd::SetActivity,
+            ActivityArgs {
                 pid: std::process::id(),
                 activity: Some(activity),
             },
-            nonce: Uuid::new_v4().to_string(),
-            evt: None,
-        }
+            None,
+        )
     }
 
     fn clear_activity() -> Self {
-        Self {
-            cmd: ActivityCmd::SetActivity,
-            args: ActivityArgs {
+        Self::new(
+            ActivityCmd::SetActivity,
+            ActivityArgs {
                 pid: std::process::id(),
                 activity: None,
             },
-            nonce: Uuid::new_v4().to_string(),
-            evt: None,
-        }
+            None,
+        )
     }
 }
 
 /// This defines all of the messages you can send to Discord from your app.
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum DiscordIPCPayload<'a> {
     /// Connection handshake.
     Handshake(HandshakeData),
     /// A command.
-    Command(DiscordIPCCommandOutgoing<'a>),
+    Command(DiscordIPCCommandOutgoing<ActivityArgs<'a>>),
 }
 impl serde::Serialize for DiscordIPCPayload<'_> {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -215,6 +241,24 @@ pub struct DiscordIPCCommandIncoming {
     /// The type of event this is.
     pub evt: Option<ActivityEvent>,
 }
+impl DiscordIPCCommandIncoming {
+    /// Converts this response into its payload, or the error Discord
+    /// responded with if the command failed. Error codes not known to this
+    /// crate are returned as [`Error::UnknownDiscordError`](crate::Error::UnknownDiscordError).
+    pub fn into_result(self) -> Result<Value> {
+        if self.evt != Some(ActivityEvent::Error) {
+            return Ok(self.data);
+        }
+
+        let code = self.data["code"].as_u64().unwrap_or_default() as u16;
+        let message = self.data["message"].as_str().unwrap_or_default().to_string();
+
+        Err(match DiscordIPCErrorCodeNonCritical::from_repr(code) {
+            Some(code) => DiscordIPCError { code: DiscordIPCErrorCode::NonCritical(code), message }.into(),
+            None => Error::UnknownDiscordError { code, message },
+        })
+    }
+}
 
 /// All of the possible errors from Discord (critical).
 #[allow(missing_docs)]
@@ -279,6 +323,17 @@ pub enum DiscordIPCErrorCode {
     Critical(DiscordIPCErrorCodeCritical),
     NonCritical(DiscordIPCErrorCodeNonCritical),
 }
+impl DiscordIPCErrorCode {
+    /// Whether this error means the access token of the session
+    /// is no longer valid.
+    pub fn is_token_error(&self) -> bool {
+        matches!(
+            self,
+            DiscordIPCErrorCode::Critical(DiscordIPCErrorCodeCritical::TokenRevoked)
+                | DiscordIPCErrorCode::NonCritical(DiscordIPCErrorCodeNonCritical::InvalidToken)
+        )
+    }
+}
 impl<'de> serde::Deserialize<'de> for DiscordIPCErrorCode {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
         // this is probably wrong... view: https://github.com/foxt/easy-presence/blob/master/ipc.md#error-handling
@@ -310,6 +365,14 @@ impl std::fmt::Display for DiscordIPCError {
 }
 impl std::error::Error for DiscordIPCError {}
 
+/// The payload of a close frame from Discord.
+#[derive(Deserialize, Debug)]
+struct CloseFrame {
+    code: u16,
+    #[serde(default)]
+    message: String,
+}
+
 /// The response from Discord.
 #[derive(Clone, Eq, PartialEq, strum::Display, Debug)]
 pub enum DiscordIPCResponse {
@@ -319,10 +382,7 @@ pub enum DiscordIPCResponse {
     Error(DiscordIPCError),
 }
 impl<'de> serde::Deserialize<'de> for DiscordIPCResponse {
-    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
         struct Visitor;
 
         impl<'de> serde::de::Visitor<'de> for Visitor {
@@ -364,6 +424,16 @@ impl<'de> serde::Deserialize<'de> for DiscordIPCResponse {
     }
 }
 
+/// The events of a lobby, subscribed to with [`DiscordIpc::subscribe_lobby_events`].
+const LOBBY_EVENTS: [ActivityEvent; 6] = [
+    ActivityEvent::LobbyUpdate,
+    ActivityEvent::LobbyDelete,
+    ActivityEvent::LobbyMemberConnect,
+    ActivityEvent::LobbyMemberDisconnect,
+    ActivityEvent::LobbyMemberUpdate,
+    ActivityEvent::LobbyMessage,
+];
+
 /// A client that connects to and communicates with the Discord IPC.
 ///
 /// Implemented via the [`DiscordIpcClient`](struct@crate::DiscordIpcClient) struct.
@@ -371,7 +441,10 @@ pub trait DiscordIpc {
     /// Connects the client to the Discord IPC.
     ///
     /// This method attempts to first establish a connection,
-    /// and then sends a handshake.
+    /// and then sends a handshake. If a [`TokenStore`] is set,
+    /// the session is then authenticated as with [`restore_authentication`].
+    ///
+    /// [`restore_authentication`]: #method.restore_authentication
     ///
     /// # Errors
     ///
@@ -390,6 +463,10 @@ pub trait DiscordIpc {
         self.send_handshake()?;
         log::debug!("Sent handshake to Discord IPC");
 
+        if self.get_state().token_store.is_some() {
+            self.restore_authentication()?;
+        }
+
         Ok(())
     }
 
@@ -421,11 +498,21 @@ pub trait DiscordIpc {
         self.send_handshake()?;
         log::debug!("Sent handshake to Discord IPC");
 
+        if self.get_state().token_store.is_some() {
+            self.restore_authentication()?;
+        }
+
         Ok(())
     }
 
     #[doc(hidden)]
-    fn get_client_id(&self) -> &String;
+    fn get_client_id(&self) -> &Snowflake;
+
+    #[doc(hidden)]
+    fn get_state(&self) -> &ClientState;
+
+    #[doc(hidden)]
+    fn get_state_mut(&mut self) -> &mut ClientState;
 
     #[doc(hidden)]
     fn connect_ipc(&mut self) -> Result<()>;
@@ -444,12 +531,19 @@ pub trait DiscordIpc {
     /// Returns an `Err` variant if sending the handshake failed.
     fn send_handshake(&mut self) -> Result<()> {
         self.send(
-            &HandshakeData::new(self.get_client_id()),
+            &HandshakeData::new(*self.get_client_id()),
             0,
         )?;
         // TODO: Return an Err if the handshake is rejected
         self.recv()?;
 
+        // a new session starts unauthenticated
+        let state = self.get_state_mut();
+        state.events.clear();
+        state.subscriptions.clear();
+        state.scopes.clear();
+        state.overlay = None;
+
         Ok(())
     }
 
@@ -508,6 +602,15 @@ pub trait DiscordIpc {
         let mut data = vec![0u8; length as usize];
         self.read(&mut data)?;
 
+        // Discord closes the connection with a close frame for critical errors
+        if op == 2 {
+            let close = serde_json::from_slice::<CloseFrame>(&data)?;
+            log::debug!("Received IPC close [{}]: {}", close.code, close.message);
+
+            let code = DiscordIPCErrorCodeCritical::from_repr(close.code).unwrap_or(DiscordIPCErrorCodeCritical::CloseAbnormal);
+            Err(DiscordIPCError { code: DiscordIPCErrorCode::Critical(code), message: close.message })?;
+        }
+
         let json_data = serde_json::from_slice::<DiscordIPCResponse>(&data)?;
 
         log::debug!("Received IPC message [{}]: {:?}", op, json_data);
@@ -518,28 +621,1206 @@ pub trait DiscordIpc {
     #[doc(hidden)]
     fn read(&mut self, buffer: &mut [u8]) -> Result<()>;
 
-    /// Sets a Discord activity.
+    /// Sends a command to the Discord IPC and waits for its response.
     ///
-    /// This method is an abstraction of [`send`],
-    /// wrapping it such that only an activity payload
-    /// is required.
+    /// Any events received while waiting are queued, and the payload
+    /// of the response is deserialized into `R`.
     ///
-    /// [`send`]: #method.send
+    /// If Discord rejects the access token, the token is refreshed and the
+    /// command is sent again. When Discord also closed the connection, the
+    /// client reconnects first and re-subscribes to the events it was
+    /// subscribed to.
     ///
     /// # Errors
-    /// Returns an `Err` variant if sending the payload failed.
-    fn set_activity(&mut self, activity_payload: Activity) -> Result<()> {
-        self.send(&DiscordIPCCommandOutgoing::set_activity(activity_payload), 1)
+    /// Returns an `Err` variant if sending the command or reading the
+    /// response failed, or [`Error::Discord`](crate::Error::Discord) if
+    /// Discord responded with an error.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use discord_ipc_rp::{ActivityCmd, DiscordIpc, DiscordIpcClient};
+    /// # let mut client = DiscordIpcClient::new("771124766517755954")?;
+    /// let guilds: serde_json::Value = client.send_command(ActivityCmd::GetGuilds, serde_json::json!({}))?;
+    /// # Ok::<(), discord_ipc_rp::Error>(())
+    /// ```
+    fn send_command<A: Serialize, R: DeserializeOwned>(&mut self, cmd: ActivityCmd, args: A) -> Result<R> {
+        self.send_command_with_event(cmd, None, args)
     }
 
-    /// Works the same as as [`set_activity`] but clears activity instead.
-    /// 
-    /// [`set_activity`]: #method.set_activity
-    /// 
+    #[doc(hidden)]
+    fn send_command_with_event<A: Serialize, R: DeserializeOwned>(&mut self, cmd: ActivityCmd, evt: Option<ActivityEvent>, args: A) -> Result<R> {
+        let args = serde_json::to_value(args)?;
+
+        let data = match self.send_command_raw(cmd, evt, &args) {
+            Err(Error::Discord(error)) if error.code.is_token_error() && cmd != ActivityCmd::Authenticate => {
+                log::debug!("Access token rejected, refreshing session: {}", error);
+                let subscriptions = match error.code {
+                    DiscordIPCErrorCode::Critical(_) => {
+                        // Discord closed the connection, so the new session starts without
+                        // the subscriptions of the old one, but keeps its queued events
+                        let state = self.get_state_mut();
+                        let subscriptions = std::mem::take(&mut state.subscriptions);
+                        let events = std::mem::take(&mut state.events);
+                        if let Err(e) = self.close() {
+                            log::debug!("Failed to close the connection: {}", e);
+                        }
+                        self.connect_ipc()?;
+                        self.send_handshake()?;
+                        self.get_state_mut().events = events;
+                        subscriptions
+                    }
+                    DiscordIPCErrorCode::NonCritical(_) => Vec::new(),
+                };
+                if self.refresh_authentication().is_err() {
+                    return Err(error.into());
+                }
+                for (evt, args, count) in subscriptions {
+                    self.send_command_raw(ActivityCmd::Subscribe, Some(evt), &args)?;
+                    self.get_state_mut().subscriptions.push((evt, args, count));
+                }
+                self.send_command_raw(cmd, evt, &args)?
+            }
+            result => result?,
+        };
+
+        Ok(serde_json::from_value(data)?)
+    }
+
+    #[doc(hidden)]
+    fn send_command_raw(&mut self, cmd: ActivityCmd, evt: Option<ActivityEvent>, args: &Value) -> Result<Value> {
+        let command = DiscordIPCCommandOutgoing::new(cmd, args, evt);
+        self.send(&command, 1)?;
+
+        loop {
+            match self.recv()?.1 {
+                DiscordIPCResponse::Command(response) if response.nonce.as_ref() == Some(&command.nonce) => {
+                    return response.into_result();
+                }
+                DiscordIPCResponse::Command(event) if event.cmd == ActivityCmd::Dispatch => self.get_state_mut().events.push_back(event),
+                DiscordIPCResponse::Command(response) => log::debug!("Ignoring unexpected response: {:?}", response),
+                DiscordIPCResponse::Error(error) => return Err(error.into()),
+            }
+        }
+    }
+
+    /// Subscribes to an event.
+    ///
+    /// Some events are scoped to a channel, guild or lobby, in which case
+    /// `args` contains its ID (e.g. `{ "channel_id": ... }`). Dispatched
+    /// events are received with [`recv_event`].
+    ///
+    /// [`recv_event`]: #method.recv_event
+    ///
+    /// Subscriptions are counted, so that helpers sharing an event do not
+    /// unsubscribe each other: Discord is only unsubscribed once
+    /// [`unsubscribe`] was called as many times as `subscribe`.
+    ///
+    /// [`unsubscribe`]: #method.unsubscribe
+    ///
     /// # Errors
-    /// Returns an `Err` variant if sending the payload failed.
-    fn clear_activity(&mut self) -> Result<()> {
-        self.send(&DiscordIPCCommandOutgoing::clear_activity(), 1)
+    /// Returns an `Err` variant if the command failed.
+    fn subscribe<A: Serialize>(&mut self, evt: ActivityEvent, args: A) -> Result<()> {
+        let args = serde_json::to_value(args)?;
+        let _: IgnoredAny = self.send_command_with_event(ActivityCmd::Subscribe, Some(evt), &args)?;
+
+        self.get_state_mut().hold_subscription(evt, args);
+
+        Ok(())
+    }
+
+    /// Unsubscribes from an event, with the same arguments it was
+    /// subscribed to with.
+    ///
+    /// If the event was subscribed to several times, no command is sent
+    /// until the last subscription is released.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn unsubscribe<A: Serialize>(&mut self, evt: ActivityEvent, args: A) -> Result<()> {
+        let args = serde_json::to_value(args)?;
+        if self.get_state_mut().release_subscription(evt, &args) {
+            let _: IgnoredAny = self.send_command_with_event(ActivityCmd::Unsubscribe, Some(evt), &args)?;
+        }
+
+        Ok(())
+    }
+
+    #[doc(hidden)]
+    fn unsubscribe_without_reply<A: Serialize>(&mut self, evt: ActivityEvent, args: A) -> Result<()> {
+        let args = serde_json::to_value(args)?;
+        if self.get_state_mut().release_subscription(evt, &args) {
+            // the response is skipped as an unexpected response when it is received
+            self.send(&DiscordIPCCommandOutgoing::new(ActivityCmd::Unsubscribe, &args, Some(evt)), 1)?;
+        }
+
+        Ok(())
+    }
+
+    /// Receives the next event dispatched by Discord, blocking until one
+    /// is available.
+    ///
+    /// Events received while waiting for the response to a command are
+    /// returned first, in the order they were received.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if reading the socket failed, or if the
+    /// event could not be parsed.
+    fn recv_event(&mut self) -> Result<Event> {
+        let incoming = match self.get_state_mut().events.pop_front() {
+            Some(event) => event,
+            None => loop {
+                match self.recv()?.1 {
+                    DiscordIPCResponse::Command(event) if event.cmd == ActivityCmd::Dispatch => break event,
+                    DiscordIPCResponse::Command(response) => log::debug!("Ignoring unexpected response: {:?}", response),
+                    DiscordIPCResponse::Error(error) => return Err(error.into()),
+                }
+            },
+        };
+
+        let event = Event::try_from(incoming)?;
+        if let Event::Overlay(overlay) | Event::OverlayUpdate(overlay) = &event {
+            self.get_state_mut().overlay = Some(*overlay);
+        }
+
+        Ok(event)
+    }
+
+    /// Requests authorization from the user for the given scopes, and
+    /// returns the OAuth2 authorization code.
+    ///
+    /// The user is prompted in their Discord client. The code must then be
+    /// exchanged for an access token, which is passed to [`authenticate`].
+    ///
+    /// [`authenticate`]: #method.authenticate
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed, or the user
+    /// denied the authorization.
+    fn authorize(&mut self, scopes: &[Scope], rpc_token: Option<&str>) -> Result<String> {
+        let args = AuthorizeArgs {
+            client_id: *self.get_client_id(),
+            scopes,
+            rpc_token,
+        };
+        let response: AuthorizeResponse = self.send_command(ActivityCmd::Authorize, args)?;
+
+        Ok(response.code)
+    }
+
+    /// Authenticates the session with an OAuth2 access token.
+    ///
+    /// On success, the scopes of the session are updated to the scopes
+    /// granted to the token. If Discord rejects the token and a
+    /// [`TokenExchanger`] is set, the stored token is refreshed and
+    /// the session is re-authenticated with it.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed, or the
+    /// access token is invalid.
+    fn authenticate(&mut self, access_token: &str) -> Result<AuthenticateResponse> {
+        let args = serde_json::to_value(AuthenticateArgs { access_token })?;
+
+        let data = match self.send_command_raw(ActivityCmd::Authenticate, None, &args) {
+            Err(Error::Discord(error)) if error.code.is_token_error() => {
+                let can_refresh = self.get_state().token_exchanger.is_some()
+                    && self.get_state().token.as_ref().is_some_and(|stored| stored.token.access_token == access_token && stored.token.refresh_token.is_some());
+                if !can_refresh {
+                    return Err(error.into());
+                }
+                if let DiscordIPCErrorCode::Critical(_) = error.code {
+                    self.connect_ipc()?;
+                    self.send_handshake()?;
+                }
+                return self.refresh_authentication();
+            }
+            result => result?,
+        };
+
+        let response: AuthenticateResponse = serde_json::from_value(data)?;
+        self.get_state_mut().scopes = response.scopes.clone();
+
+        Ok(response)
+    }
+
+    /// Sets the [`TokenExchanger`] used to exchange authorization codes
+    /// for access tokens, and to refresh them once they are rejected.
+    fn set_token_exchanger<T: TokenExchanger + 'static>(&mut self, exchanger: T) {
+        self.get_state_mut().token_exchanger = Some(Box::new(exchanger));
+    }
+
+    /// Authorizes the given scopes, exchanges the authorization code for an
+    /// access token with the [`TokenExchanger`], and authenticates with it.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if no token exchanger is set, or if any of
+    /// the steps failed.
+    fn login(&mut self, scopes: &[Scope]) -> Result<AuthenticateResponse> {
+        if self.get_state().token_exchanger.is_none() {
+            return Err(Error::MissingTokenExchanger);
+        }
+
+        let code = self.authorize(scopes, None)?;
+        let token = self.get_state().token_exchanger.as_ref().ok_or(Error::MissingTokenExchanger)?.exchange_code(&code)?;
+        let access_token = token.access_token.clone();
+        self.save_token(token)?;
+
+        self.authenticate(&access_token)
+    }
+
+    /// Refreshes the stored access token with the [`TokenExchanger`],
+    /// and re-authenticates the session with the new token.
+    ///
+    /// This is done automatically when Discord rejects the access token.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if no token exchanger is set, if there is no
+    /// refresh token, or if refreshing or authenticating failed.
+    fn refresh_authentication(&mut self) -> Result<AuthenticateResponse> {
+        let state = self.get_state();
+        let exchanger = state.token_exchanger.as_ref().ok_or(Error::MissingTokenExchanger)?;
+        let refresh_token = state.token.as_ref().and_then(|stored| stored.token.refresh_token.clone()).ok_or(Error::MissingRefreshToken)?;
+
+        let mut token = exchanger.refresh_token(&refresh_token)?;
+        token.refresh_token.get_or_insert(refresh_token);
+        let args = serde_json::to_value(AuthenticateArgs { access_token: &token.access_token })?;
+        self.save_token(token)?;
+
+        let response: AuthenticateResponse = serde_json::from_value(self.send_command_raw(ActivityCmd::Authenticate, None, &args)?)?;
+        self.get_state_mut().scopes = response.scopes.clone();
+        log::debug!("Refreshed access token");
+
+        Ok(response)
+    }
+
+    /// Sets the [`TokenStore`] used to persist access tokens between sessions.
+    ///
+    /// Once set, [`connect`] authenticates with the stored token, and
+    /// authorizes the given scopes with [`login`] when there is no valid
+    /// stored token. Tokens obtained with [`login`] or by refreshing are saved
+    /// to the store.
+    ///
+    /// [`connect`]: #method.connect
+    /// [`login`]: #method.login
+    fn set_token_store<T: TokenStore + 'static>(&mut self, store: T, scopes: &[Scope]) {
+        let state = self.get_state_mut();
+        state.token_store = Some(Box::new(store));
+        state.login_scopes = scopes.to_vec();
+    }
+
+    /// Authenticates the session with the token saved in the [`TokenStore`].
+    ///
+    /// An expired token is refreshed first, if possible. If there is no valid
+    /// token, or it could not be loaded, the stored token is cleared and the scopes given to
+    /// [`set_token_store`] are authorized with [`login`] instead.
+    ///
+    /// [`set_token_store`]: #method.set_token_store
+    /// [`login`]: #method.login
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if clearing the store failed, or if
+    /// authorizing with [`login`] failed.
+    fn restore_authentication(&mut self) -> Result<AuthenticateResponse> {
+        let client_id = *self.get_client_id();
+        let stored = match &self.get_state().token_store {
+            Some(store) => match store.load(client_id) {
+                Ok(stored) => stored,
+                // a corrupt token is treated like a missing one
+                Err(error) => {
+                    log::warn!("Could not load the stored access token, discarding it: {}", error);
+                    store.clear(client_id)?;
+                    None
+                }
+            },
+            None => None,
+        };
+
+        if let Some(stored) = stored {
+            let access_token = stored.token.access_token.clone();
+            let expired = stored.is_expired();
+            self.get_state_mut().token = Some(stored);
+
+            let result = if expired {
+                log::debug!("Stored access token has expired");
+                self.refresh_authentication()
+            } else {
+                self.authenticate(&access_token)
+            };
+            match result {
+                Ok(response) => return Ok(response),
+                Err(Error::Discord(error)) if error.code.is_token_error() => {}
+                Err(Error::MissingTokenExchanger | Error::MissingRefreshToken | Error::TokenExchange(_)) => {}
+                Err(error) => return Err(error),
+            }
+
+            log::debug!("Stored access token is no longer valid");
+            self.get_state_mut().token = None;
+            if let Some(store) = &self.get_state().token_store {
+                store.clear(client_id)?;
+            }
+        }
+
+        let scopes = self.get_state().login_scopes.clone();
+        self.login(&scopes)
+    }
+
+    #[doc(hidden)]
+    fn save_token(&mut self, token: Token) -> Result<()> {
+        let stored = StoredToken::new(token);
+        if let Some(store) = &self.get_state().token_store {
+            store.save(*self.get_client_id(), &stored)?;
+        }
+        self.get_state_mut().token = Some(stored);
+
+        Ok(())
+    }
+
+    /// The scopes the current session is authenticated with.
+    ///
+    /// This is empty until [`authenticate`] succeeds, and is reset
+    /// whenever a new handshake is sent.
+    ///
+    /// [`authenticate`]: #method.authenticate
+    fn scopes(&self) -> &[Scope] {
+        &self.get_state().scopes
+    }
+
+    /// Whether the current session is authenticated with the given scope.
+    fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes().contains(&scope)
+    }
+
+    /// Gets a guild the user is in.
+    ///
+    /// Requires the [`Rpc`](Scope::Rpc) scope.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn get_guild(&mut self, guild_id: Snowflake) -> Result<Guild> {
+        self.send_command(ActivityCmd::GetGuild, GuildArgs { guild_id })
+    }
+
+    /// Gets the guilds the user is in.
+    ///
+    /// Requires the [`Rpc`](Scope::Rpc) scope.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn get_guilds(&mut self) -> Result<Vec<Guild>> {
+        let response: GuildsResponse = self.send_command(ActivityCmd::GetGuilds, crate::Empty)?;
+
+        Ok(response.guilds)
+    }
+
+    /// Gets a channel the user has access to.
+    ///
+    /// Requires the [`Rpc`](Scope::Rpc) scope.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn get_channel(&mut self, channel_id: Snowflake) -> Result<Channel> {
+        self.send_command(ActivityCmd::GetChannel, ChannelArgs { channel_id })
+    }
+
+    /// Gets the channels of a guild.
+    ///
+    /// Requires the [`Rpc`](Scope::Rpc) scope.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn get_channels(&mut self, guild_id: Snowflake) -> Result<Vec<Channel>> {
+        let response: ChannelsResponse = self.send_command(ActivityCmd::GetChannels, GuildArgs { guild_id })?;
+
+        Ok(response.channels)
+    }
+
+    /// Creates an invite to a channel.
+    ///
+    /// Requires the [`Rpc`](Scope::Rpc) scope.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn create_channel_invite(&mut self, channel_id: Snowflake) -> Result<Invite> {
+        self.send_command(ActivityCmd::CreateChannelInvite, ChannelArgs { channel_id })
+    }
+
+    /// Moves the user into a voice channel, and returns the channel.
+    ///
+    /// If the user is already in another voice channel, `force` must be set
+    /// to move them, otherwise [`Error::SelectVoiceForceRequired`] is
+    /// returned. If the channel could not be joined within `timeout`,
+    /// [`Error::SelectChannelTimedOut`] is returned.
+    ///
+    /// Requires the [`Rpc`](Scope::Rpc) scope.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn select_voice_channel(&mut self, channel_id: Snowflake, force: bool, timeout: Option<Duration>) -> Result<Channel> {
+        let args = SelectChannelArgs {
+            channel_id: Some(channel_id),
+            timeout,
+            force,
+        };
+
+        self.send_command(ActivityCmd::SelectVoiceChannel, args)
+    }
+
+    /// Moves the user out of their current voice channel.
+    ///
+    /// Requires the [`Rpc`](Scope::Rpc) scope.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn leave_voice_channel(&mut self) -> Result<()> {
+        let args = SelectChannelArgs { channel_id: None, timeout: None, force: false };
+        let _: IgnoredAny = self.send_command(ActivityCmd::SelectVoiceChannel, args)?;
+
+        Ok(())
+    }
+
+    /// Gets the voice channel the user is in, if any.
+    ///
+    /// Requires the [`Rpc`](Scope::Rpc) scope.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn get_selected_voice_channel(&mut self) -> Result<Option<Channel>> {
+        self.send_command(ActivityCmd::GetSelectedVoiceChannel, crate::Empty)
+    }
+
+    /// Navigates the user to a text channel, and returns the channel.
+    ///
+    /// If the channel could not be selected within `timeout`,
+    /// [`Error::SelectChannelTimedOut`] is returned.
+    ///
+    /// Requires the [`Rpc`](Scope::Rpc) scope.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn select_text_channel(&mut self, channel_id: Snowflake, timeout: Option<Duration>) -> Result<Channel> {
+        let args = SelectChannelArgs {
+            channel_id: Some(channel_id),
+            timeout,
+            force: false,
+        };
+
+        self.send_command(ActivityCmd::SelectTextChannel, args)
+    }
+
+    /// Sets the local voice settings of another user, such as their volume,
+    /// and returns their updated settings.
+    ///
+    /// The current settings of a user can be read from their
+    /// [`VoiceState`](crate::models::VoiceState).
+    ///
+    /// Requires the [`RpcVoiceWrite`](Scope::RpcVoiceWrite) scope.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use discord_ipc_rp::{models::{Snowflake, UserVoiceSettings}, DiscordIpc, DiscordIpcClient};
+    /// # let mut client = DiscordIpcClient::new("771124766517755954")?;
+    /// # let user_id = Snowflake::new(53908232506183680);
+    /// let settings = UserVoiceSettings::new(user_id).volume(150.0);
+    /// client.set_user_voice_settings(&settings)?;
+    /// # Ok::<(), discord_ipc_rp::Error>(())
+    /// ```
+    fn set_user_voice_settings(&mut self, settings: &UserVoiceSettings) -> Result<UserVoiceSettings> {
+        self.send_command(ActivityCmd::SetUserVoiceSettings, settings)
+    }
+
+    /// Gets the voice settings of the user.
+    ///
+    /// Requires the [`RpcVoiceRead`](Scope::RpcVoiceRead) scope.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn get_voice_settings(&mut self) -> Result<VoiceSettings> {
+        self.send_command(ActivityCmd::GetVoiceSettings, crate::Empty)
+    }
+
+    /// Sets the voice settings of the user, and returns the updated settings.
+    ///
+    /// The current settings are fetched first, and only the fields that
+    /// differ from them are sent, so other settings the user changed in the
+    /// meantime are not overwritten. If nothing differs, no changes are sent
+    /// and the current settings are returned.
+    ///
+    /// Requires the [`RpcVoiceWrite`](Scope::RpcVoiceWrite) scope.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use discord_ipc_rp::{DiscordIpc, DiscordIpcClient};
+    /// # let mut client = DiscordIpcClient::new("771124766517755954")?;
+    /// let mut settings = client.get_voice_settings()?;
+    /// settings.mute = !settings.mute;
+    /// client.set_voice_settings(&settings)?;
+    /// # Ok::<(), discord_ipc_rp::Error>(())
+    /// ```
+    fn set_voice_settings(&mut self, settings: &VoiceSettings) -> Result<VoiceSettings> {
+        let current = self.get_voice_settings()?;
+
+        let changes = settings.changes_from(&current)?;
+        if changes.as_object().is_some_and(|changes| changes.is_empty()) {
+            return Ok(current);
+        }
+
+        self.send_command(ActivityCmd::SetVoiceSettings, changes)
+    }
+
+    /// Starts capturing a shortcut. While capturing, the keys the user presses
+    /// are dispatched in [`CaptureShortcutChange`](crate::events::Event::CaptureShortcutChange)
+    /// events, until [`stop_capture_shortcut`](Self::stop_capture_shortcut) is called.
+    ///
+    /// The captured keys can be used as the push to talk shortcut, with
+    /// [`set_voice_settings`](Self::set_voice_settings).
+    ///
+    /// # Errors
+    /// Returns [`Error::CaptureShortcutAlreadyListening`](crate::Error::CaptureShortcutAlreadyListening)
+    /// if a shortcut is already being captured, or an `Err` variant if the command failed.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use discord_ipc_rp::{events::Event, DiscordIpc, DiscordIpcClient};
+    /// # let mut client = DiscordIpcClient::new("771124766517755954")?;
+    /// client.start_capture_shortcut()?;
+    /// let shortcut = loop {
+    ///     if let Event::CaptureShortcutChange(change) = client.recv_event()? {
+    ///         break change.shortcut;
+    ///     }
+    /// };
+    /// client.stop_capture_shortcut()?;
+    /// # drop(shortcut);
+    /// # Ok::<(), discord_ipc_rp::Error>(())
+    /// ```
+    fn start_capture_shortcut(&mut self) -> Result<()> {
+        let args = CaptureShortcutArgs { action: CaptureShortcutAction::Start };
+        let _: IgnoredAny = self.send_command(ActivityCmd::CaptureShortcut, args)?;
+
+        Ok(())
+    }
+
+    /// Stops capturing a shortcut started with [`start_capture_shortcut`](Self::start_capture_shortcut).
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn stop_capture_shortcut(&mut self) -> Result<()> {
+        let args = CaptureShortcutArgs { action: CaptureShortcutAction::Stop };
+        let _: IgnoredAny = self.send_command(ActivityCmd::CaptureShortcut, args)?;
+
+        Ok(())
+    }
+
+    /// Sets a Discord activity.
+    ///
+    /// This method is an abstraction of [`send`],
+    /// wrapping it such that only an activity payload
+    /// is required.
+    ///
+    /// [`send`]: #method.send
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if sending the payload failed.
+    fn set_activity(&mut self, activity_payload: Activity) -> Result<()> {
+        self.send(&DiscordIPCCommandOutgoing::set_activity(activity_payload), 1)
+    }
+
+    /// Works the same as as [`set_activity`] but clears activity instead.
+    /// 
+    /// [`set_activity`]: #method.set_activity
+    /// 
+    /// # Errors
+    /// Returns an `Err` variant if sending the payload failed.
+    fn clear_activity(&mut self) -> Result<()> {
+        self.send(&DiscordIPCCommandOutgoing::clear_activity(), 1)
+    }
+
+    /// Subscribes to the events of the join and spectate flow:
+    /// [`ActivityJoin`](ActivityEvent::ActivityJoin),
+    /// [`ActivitySpectate`](ActivityEvent::ActivitySpectate),
+    /// [`ActivityJoinRequest`](ActivityEvent::ActivityJoinRequest) and
+    /// [`ActivityInvite`](ActivityEvent::ActivityInvite).
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if subscribing failed.
+    fn subscribe_activity_events(&mut self) -> Result<()> {
+        for evt in [
+            ActivityEvent::ActivityJoin,
+            ActivityEvent::ActivitySpectate,
+            ActivityEvent::ActivityJoinRequest,
+            ActivityEvent::ActivityInvite,
+        ] {
+            self.subscribe(evt, crate::Empty)?;
+        }
+
+        Ok(())
+    }
+
+    /// Accepts the request of a user to join the activity of the user,
+    /// as received in an [`ActivityJoinRequest`](crate::events::Event::ActivityJoinRequest) event.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn send_activity_join_invite(&mut self, user_id: Snowflake) -> Result<()> {
+        let _: IgnoredAny = self.send_command(ActivityCmd::SendActivityJoinInvite, UserArgs { user_id })?;
+
+        Ok(())
+    }
+
+    /// Rejects the request of a user to join the activity of the user,
+    /// as received in an [`ActivityJoinRequest`](crate::events::Event::ActivityJoinRequest) event.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn close_activity_join_request(&mut self, user_id: Snowflake) -> Result<()> {
+        let _: IgnoredAny = self.send_command(ActivityCmd::CloseActivityJoinRequest, UserArgs { user_id })?;
+
+        Ok(())
+    }
+
+    /// Invites a user to join or spectate the activity of the user, with
+    /// a message. The activity must have a party and the matching secret.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn activity_invite_user(&mut self, user_id: Snowflake, kind: ActivityActionType, content: &str) -> Result<()> {
+        let args = ActivityInviteUserArgs {
+            user_id,
+            kind,
+            content,
+            pid: std::process::id(),
+        };
+
+        let _: IgnoredAny = self.send_command(ActivityCmd::ActivityInviteUser, args)?;
+
+        Ok(())
+    }
+
+    /// Accepts an invite to an activity, as received in an
+    /// [`ActivityInvite`](crate::events::Event::ActivityInvite) event.
+    ///
+    /// Discord then launches the game if needed, and dispatches an
+    /// [`ActivityJoin`](crate::events::Event::ActivityJoin) event with the
+    /// join secret.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn accept_activity_invite(&mut self, invite: &ActivityInvite) -> Result<()> {
+        let args = AcceptActivityInviteArgs {
+            kind: invite.kind,
+            user_id: invite.user.id,
+            session_id: invite.activity.session_id.as_deref(),
+            channel_id: invite.channel_id,
+            message_id: invite.message_id,
+        };
+
+        let _: IgnoredAny = self.send_command(ActivityCmd::AcceptActivityInvite, args)?;
+
+        Ok(())
+    }
+
+    /// Creates a lobby owned by the user, and returns it.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use discord_ipc_rp::{models::{LobbyOptions, LobbyType}, DiscordIpc, DiscordIpcClient};
+    /// # let mut client = DiscordIpcClient::new("771124766517755954")?;
+    /// let lobby = client.create_lobby(&LobbyOptions::new().kind(LobbyType::Public).capacity(4))?;
+    /// # Ok::<(), discord_ipc_rp::Error>(())
+    /// ```
+    fn create_lobby(&mut self, options: &LobbyOptions) -> Result<Lobby> {
+        self.send_command(ActivityCmd::CreateLobby, options)
+    }
+
+    /// Updates the properties of a lobby owned by the user.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidLobby`](crate::Error::InvalidLobby) if the lobby
+    /// does not exist, or an `Err` variant if the command failed.
+    fn update_lobby(&mut self, lobby_id: Snowflake, options: &LobbyOptions) -> Result<()> {
+        let _: IgnoredAny = self.send_command(ActivityCmd::UpdateLobby, UpdateLobbyArgs { id: lobby_id, options })?;
+
+        Ok(())
+    }
+
+    /// Deletes a lobby owned by the user.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidLobby`](crate::Error::InvalidLobby) if the lobby
+    /// does not exist, or an `Err` variant if the command failed.
+    fn delete_lobby(&mut self, lobby_id: Snowflake) -> Result<()> {
+        let _: IgnoredAny = self.send_command(ActivityCmd::DeleteLobby, LobbyArgs { id: lobby_id })?;
+
+        Ok(())
+    }
+
+    /// Sets the metadata of a member of a lobby owned by the user.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidLobby`](crate::Error::InvalidLobby) if the lobby
+    /// does not exist, or an `Err` variant if the command failed.
+    fn update_lobby_member(&mut self, lobby_id: Snowflake, user_id: Snowflake, metadata: &BTreeMap<String, String>) -> Result<()> {
+        let args = UpdateLobbyMemberArgs { lobby_id, user_id, metadata };
+        let _: IgnoredAny = self.send_command(ActivityCmd::UpdateLobbyMember, args)?;
+
+        Ok(())
+    }
+
+    /// Joins a lobby with its secret, and returns it.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidLobbySecret`](crate::Error::InvalidLobbySecret) if
+    /// the secret is invalid, [`Error::LobbyFull`](crate::Error::LobbyFull) if the
+    /// lobby is full, or an `Err` variant if the command failed.
+    fn connect_to_lobby(&mut self, lobby_id: Snowflake, secret: &str) -> Result<Lobby> {
+        self.send_command(ActivityCmd::ConnectToLobby, ConnectToLobbyArgs { id: lobby_id, secret })
+    }
+
+    /// Leaves a lobby.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidLobby`](crate::Error::InvalidLobby) if the user is
+    /// not a member of the lobby, or an `Err` variant if the command failed.
+    fn disconnect_from_lobby(&mut self, lobby_id: Snowflake) -> Result<()> {
+        let _: IgnoredAny = self.send_command(ActivityCmd::DisconnectFromLobby, LobbyArgs { id: lobby_id })?;
+
+        Ok(())
+    }
+
+    /// Searches the public lobbies of the application.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use discord_ipc_rp::{models::{LobbySearchCast, LobbySearchComparison, LobbySearchQuery}, DiscordIpc, DiscordIpcClient};
+    /// # let mut client = DiscordIpcClient::new("771124766517755954")?;
+    /// let query = LobbySearchQuery::new()
+    ///     .filter("mode", LobbySearchComparison::Equal, "ranked", LobbySearchCast::String)
+    ///     .limit(10);
+    /// let lobbies = client.search_lobbies(&query)?;
+    /// # Ok::<(), discord_ipc_rp::Error>(())
+    /// ```
+    fn search_lobbies(&mut self, query: &LobbySearchQuery) -> Result<Vec<Lobby>> {
+        self.send_command(ActivityCmd::SearchLobbies, query)
+    }
+
+    /// Sends a message to the members of a lobby. The data is transmitted
+    /// as base64, and is received in [`LobbyMessage`](crate::events::Event::LobbyMessage)
+    /// events.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidLobby`](crate::Error::InvalidLobby) if the user is
+    /// not a member of the lobby, or an `Err` variant if the command failed.
+    fn send_to_lobby(&mut self, lobby_id: Snowflake, data: &[u8]) -> Result<()> {
+        let _: IgnoredAny = self.send_command(ActivityCmd::SendToLobby, SendToLobbyArgs { lobby_id, data })?;
+
+        Ok(())
+    }
+
+    /// Joins the voice channel of a lobby.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidLobby`](crate::Error::InvalidLobby) if the user is
+    /// not a member of the lobby, or an `Err` variant if the command failed.
+    fn connect_to_lobby_voice(&mut self, lobby_id: Snowflake) -> Result<()> {
+        let _: IgnoredAny = self.send_command(ActivityCmd::ConnectToLobbyVoice, LobbyArgs { id: lobby_id })?;
+
+        Ok(())
+    }
+
+    /// Leaves the voice channel of a lobby.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidLobby`](crate::Error::InvalidLobby) if the user is
+    /// not a member of the lobby, or an `Err` variant if the command failed.
+    fn disconnect_from_lobby_voice(&mut self, lobby_id: Snowflake) -> Result<()> {
+        let _: IgnoredAny = self.send_command(ActivityCmd::DisconnectFromLobbyVoice, LobbyArgs { id: lobby_id })?;
+
+        Ok(())
+    }
+
+    /// Subscribes to the events of a lobby: updates, deletion, members
+    /// connecting, disconnecting and being updated, and messages.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if subscribing failed.
+    fn subscribe_lobby_events(&mut self, lobby_id: Snowflake) -> Result<()> {
+        for evt in LOBBY_EVENTS {
+            self.subscribe(evt, LobbyEventArgs { lobby_id })?;
+        }
+
+        Ok(())
+    }
+
+    /// Unsubscribes from the events of a lobby subscribed to with
+    /// [`subscribe_lobby_events`](Self::subscribe_lobby_events).
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if unsubscribing failed.
+    fn unsubscribe_lobby_events(&mut self, lobby_id: Snowflake) -> Result<()> {
+        for evt in LOBBY_EVENTS {
+            self.unsubscribe(evt, LobbyEventArgs { lobby_id })?;
+        }
+
+        Ok(())
+    }
+
+    /// Gets the SKUs of the store of the application.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn get_skus(&mut self) -> Result<Vec<Sku>> {
+        self.send_command(ActivityCmd::GetSkus, crate::Empty)
+    }
+
+    /// Gets the entitlements of the user to the SKUs of the application.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn get_entitlements(&mut self) -> Result<Vec<Entitlement>> {
+        self.send_command(ActivityCmd::GetEntitlements, crate::Empty)
+    }
+
+    /// Checks whether the user owns a SKU, such as downloadable content.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the entitlements could not be fetched.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use discord_ipc_rp::{models::Snowflake, DiscordIpc, DiscordIpcClient};
+    /// # let mut client = DiscordIpcClient::new("771124766517755954")?;
+    /// # let dlc_sku_id = Snowflake::new(771124766517755955);
+    /// # fn unlock_dlc() {}
+    /// if client.has_entitlement(dlc_sku_id)? {
+    ///     unlock_dlc();
+    /// }
+    /// # Ok::<(), discord_ipc_rp::Error>(())
+    /// ```
+    fn has_entitlement(&mut self, sku_id: Snowflake) -> Result<bool> {
+        Ok(self.get_entitlements()?.iter().any(|entitlement| entitlement.sku_id == sku_id))
+    }
+
+    /// Opens the purchase flow of a SKU in Discord. Once purchased, the user
+    /// is granted an entitlement, dispatched in an
+    /// [`EntitlementCreate`](crate::events::Event::EntitlementCreate) event.
+    ///
+    /// # Errors
+    /// Returns [`Error::PurchaseCanceled`](crate::Error::PurchaseCanceled) if the
+    /// user canceled the purchase, [`Error::PurchaseFailed`](crate::Error::PurchaseFailed)
+    /// if it failed, or an `Err` variant if the command failed.
+    fn start_purchase(&mut self, sku_id: Snowflake) -> Result<()> {
+        let args = StartPurchaseArgs { sku_id, pid: std::process::id() };
+        let _: IgnoredAny = self.send_command(ActivityCmd::StartPurchase, args)?;
+
+        Ok(())
+    }
+
+    /// Subscribes to the events of the entitlements of the user:
+    /// [`EntitlementCreate`](ActivityEvent::EntitlementCreate) and
+    /// [`EntitlementDelete`](ActivityEvent::EntitlementDelete).
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if subscribing failed.
+    fn subscribe_entitlement_events(&mut self) -> Result<()> {
+        self.subscribe(ActivityEvent::EntitlementCreate, crate::Empty)?;
+        self.subscribe(ActivityEvent::EntitlementDelete, crate::Empty)
+    }
+
+    /// Sets the progress of the user towards an achievement of the application.
+    ///
+    /// # Errors
+    /// Returns [`Error::UnauthorizedForAchievement`](crate::Error::UnauthorizedForAchievement)
+    /// if the application is not allowed to update the achievement, or an `Err`
+    /// variant if the command failed.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use discord_ipc_rp::{models::{AchievementProgress, Snowflake}, DiscordIpc, DiscordIpcClient};
+    /// # let mut client = DiscordIpcClient::new("771124766517755954")?;
+    /// # let achievement_id = Snowflake::new(771124766517755956);
+    /// client.set_user_achievement(achievement_id, &AchievementProgress::new(50).metadata("level", "3"))?;
+    /// # Ok::<(), discord_ipc_rp::Error>(())
+    /// ```
+    fn set_user_achievement(&mut self, achievement_id: Snowflake, progress: &AchievementProgress) -> Result<()> {
+        let args = SetUserAchievementArgs { achievement_id, progress };
+        let _: IgnoredAny = self.send_command(ActivityCmd::SetUserAchievement, args)?;
+
+        Ok(())
+    }
+
+    /// Gets the progress of the user towards the achievements of the application.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn get_user_achievements(&mut self) -> Result<Vec<UserAchievement>> {
+        self.send_command(ActivityCmd::GetUserAchievements, crate::Empty)
+    }
+
+    /// Checks that the user owns the application, such as to prevent piracy.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidEntitlement`](crate::Error::InvalidEntitlement) if the
+    /// user does not own the application, or an `Err` variant if the command failed.
+    fn validate_application(&mut self) -> Result<()> {
+        let _: IgnoredAny = self.send_command(ActivityCmd::ValidateApplication, crate::Empty)?;
+
+        Ok(())
+    }
+
+    /// Gets a ticket signed by Discord, proving who the user is, to verify on
+    /// the server of the application.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidTicket`](crate::Error::InvalidTicket) if the ticket
+    /// is malformed, or an `Err` variant if the command failed.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use discord_ipc_rp::{DiscordIpc, DiscordIpcClient};
+    /// # let mut client = DiscordIpcClient::new("771124766517755954")?;
+    /// # fn send_to_server(_: &str) {}
+    /// let ticket = client.get_application_ticket()?;
+    /// println!("logged in as {:?}", ticket.claims.user_id);
+    /// send_to_server(&ticket.ticket);
+    /// # Ok::<(), discord_ipc_rp::Error>(())
+    /// ```
+    fn get_application_ticket(&mut self) -> Result<SignedTicket> {
+        let ticket: TicketResponse = self.send_command(ActivityCmd::GetApplicationTicket, crate::Empty)?;
+
+        ticket.parse()
+    }
+
+    /// Gets a ticket signed by Discord, proving what the user owns, to verify
+    /// on the server of the application.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidTicket`](crate::Error::InvalidTicket) if the ticket
+    /// is malformed, or an `Err` variant if the command failed.
+    fn get_entitlement_ticket(&mut self) -> Result<SignedTicket> {
+        let ticket: TicketResponse = self.send_command(ActivityCmd::GetEntitlementTicket, crate::Empty)?;
+
+        ticket.parse()
+    }
+
+    /// Subscribes to the state of the overlay:
+    /// [`Overlay`](ActivityEvent::Overlay) and
+    /// [`OverlayUpdate`](ActivityEvent::OverlayUpdate). The state
+    /// is then available from [`overlay`](Self::overlay).
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if subscribing failed.
+    fn subscribe_overlay_events(&mut self) -> Result<()> {
+        self.subscribe(ActivityEvent::Overlay, OverlayArgs { pid: std::process::id() })?;
+        self.subscribe(ActivityEvent::OverlayUpdate, OverlayArgs { pid: std::process::id() })
+    }
+
+    /// The last known state of the overlay, as received in overlay events
+    /// subscribed to with [`subscribe_overlay_events`](Self::subscribe_overlay_events).
+    /// `None` until the first event is received.
+    fn overlay(&self) -> Option<OverlayState> {
+        self.get_state().overlay
+    }
+
+    /// Locks or unlocks the overlay. While locked, the overlay is shown and
+    /// receives the input of the user instead of the application.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn set_overlay_locked(&mut self, locked: bool) -> Result<()> {
+        let args = SetOverlayLockedArgs { locked, pid: std::process::id() };
+        let _: IgnoredAny = self.send_command(ActivityCmd::SetOverlayLocked, args)?;
+        if let Some(overlay) = &mut self.get_state_mut().overlay {
+            overlay.locked = locked;
+        }
+
+        Ok(())
+    }
+
+    /// Opens the overlay panel to invite users to join or spectate the
+    /// activity of the user.
+    ///
+    /// # Errors
+    /// Returns [`Error::Discord`](crate::Error::Discord) with
+    /// [`NoEligibleActivity`](DiscordIPCErrorCodeNonCritical::NoEligibleActivity)
+    /// if the activity of the user cannot be joined, or an `Err` variant if the command failed.
+    fn open_overlay_activity_invite(&mut self, kind: ActivityActionType) -> Result<()> {
+        let args = OpenOverlayActivityInviteArgs { kind, pid: std::process::id() };
+        let _: IgnoredAny = self.send_command(ActivityCmd::OpenOverlayActivityInvite, args)?;
+
+        Ok(())
+    }
+
+    /// Opens the overlay panel to join a guild, from an invite code.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn open_overlay_guild_invite(&mut self, code: &str) -> Result<()> {
+        let args = OpenOverlayGuildInviteArgs { code, pid: std::process::id() };
+        let _: IgnoredAny = self.send_command(ActivityCmd::OpenOverlayGuildInvite, args)?;
+
+        Ok(())
+    }
+
+    /// Opens the voice settings of the user in the overlay.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn open_overlay_voice_settings(&mut self) -> Result<()> {
+        let args = OverlayArgs { pid: std::process::id() };
+        let _: IgnoredAny = self.send_command(ActivityCmd::OpenOverlayVoiceSettings, args)?;
+
+        Ok(())
+    }
+
+    /// Opens a location in the Discord client, such as a channel or the
+    /// settings of the user.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use discord_ipc_rp::{models::{DeepLink, Snowflake}, DiscordIpc, DiscordIpcClient};
+    /// # let mut client = DiscordIpcClient::new("771124766517755954")?;
+    /// # let (guild_id, channel_id) = (Snowflake::new(199737254929760256), Snowflake::new(199737254929760257));
+    /// client.deep_link(&DeepLink::Channel { guild_id: Some(guild_id), channel_id, message_id: None })?;
+    /// # Ok::<(), discord_ipc_rp::Error>(())
+    /// ```
+    fn deep_link(&mut self, link: &DeepLink) -> Result<()> {
+        let _: IgnoredAny = self.send_command(ActivityCmd::DeepLink, link)?;
+
+        Ok(())
+    }
+
+    /// Opens the prompt to join a guild from an invite code, as in
+    /// `https://discord.gg/<code>`, in the Discord client.
+    ///
+    /// # Errors
+    /// Returns [`Error::Discord`](crate::Error::Discord) with
+    /// [`InvalidInvite`](DiscordIPCErrorCodeNonCritical::InvalidInvite)
+    /// if the invite is invalid, or an `Err` variant if the command failed.
+    fn invite_browser(&mut self, code: &str) -> Result<()> {
+        let _: IgnoredAny = self.send_command(ActivityCmd::InviteBrowser, CodeArgs { code })?;
+
+        Ok(())
+    }
+
+    /// Opens the prompt to create a guild from a guild template code, as in
+    /// `https://discord.new/<code>`, in the Discord client.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn guild_template_browser(&mut self, code: &str) -> Result<()> {
+        let _: IgnoredAny = self.send_command(ActivityCmd::GuildTemplateBrowser, CodeArgs { code })?;
+
+        Ok(())
+    }
+
+    /// Opens the prompt to redeem a gift code, as in
+    /// `https://discord.gift/<code>`, in the Discord client.
+    ///
+    /// # Errors
+    /// Returns [`Error::Discord`](crate::Error::Discord) with
+    /// [`InvalidGiftCode`](DiscordIPCErrorCodeNonCritical::InvalidGiftCode)
+    /// if the gift code is invalid, or an `Err` variant if the command failed.
+    fn gift_code_browser(&mut self, code: &str) -> Result<()> {
+        let _: IgnoredAny = self.send_command(ActivityCmd::GiftCodeBrowser, CodeArgs { code })?;
+
+        Ok(())
+    }
+
+    /// Hands the session of the user over from a browser to the Discord
+    /// client, logging it in.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn browser_handoff(&mut self, handoff_token: &str, fingerprint: &str) -> Result<()> {
+        let args = BrowserHandoffArgs { handoff_token, fingerprint };
+        let _: IgnoredAny = self.send_command(ActivityCmd::BrowserHandoff, args)?;
+
+        Ok(())
+    }
+
+    /// Fetches the avatar of a user through the Discord client, without
+    /// accessing the network from this process.
+    ///
+    /// The latest avatars are kept in memory, so fetching the same avatar
+    /// again does not send a command. See
+    /// [`set_avatar_cache_capacity`](Self::set_avatar_cache_capacity).
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidImageSize`](crate::Error::InvalidImageSize) if `size`
+    /// is not a power of two between 16 and 1024, [`Error::InvalidImage`](crate::Error::InvalidImage)
+    /// if the image could not be decoded, or an `Err` variant if the command failed.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use discord_ipc_rp::{models::{ImageFormat, Snowflake}, DiscordIpc, DiscordIpcClient};
+    /// # let mut client = DiscordIpcClient::new("771124766517755954")?;
+    /// # let user_id = Snowflake::new(53908232506183680);
+    /// # struct Texture;
+    /// # impl Texture { fn upload(&mut self, _: &str, _: &[u8]) {} }
+    /// # let mut texture = Texture;
+    /// let avatar = client.get_user_avatar(user_id, 128, ImageFormat::Png)?;
+    /// texture.upload(&avatar.mime_type, &avatar.data);
+    /// # Ok::<(), discord_ipc_rp::Error>(())
+    /// ```
+    fn get_user_avatar(&mut self, user_id: Snowflake, size: u16, format: ImageFormat) -> Result<Arc<Image>> {
+        if !(16..=1024).contains(&size) || !size.is_power_of_two() {
+            return Err(Error::InvalidImageSize(size));
+        }
+
+        let key = (user_id, size, format);
+        if let Some(image) = self.get_state_mut().avatars.get(&key) {
+            return Ok(image);
+        }
+
+        let args = GetImageArgs { kind: "user", id: user_id, format, size };
+        let response: GetImageResponse = self.send_command(ActivityCmd::GetImage, args)?;
+        let image = Arc::new(Image::from_data_uri(&response.data_url)?);
+        self.get_state_mut().avatars.insert(key, image.clone());
+
+        Ok(image)
+    }
+
+    /// Sets how many avatars fetched with [`get_user_avatar`](Self::get_user_avatar)
+    /// are kept in memory, 64 by default. The least recently used avatars are
+    /// evicted first.
+    fn set_avatar_cache_capacity(&mut self, capacity: usize) {
+        self.get_state_mut().avatars.set_capacity(capacity);
+    }
+
+    /// Gets a user.
+    ///
+    /// # Errors
+    /// Returns [`Error::Discord`](crate::Error::Discord) with
+    /// [`InvalidUser`](DiscordIPCErrorCodeNonCritical::InvalidUser)
+    /// if the user does not exist, or an `Err` variant if the command failed.
+    fn get_user(&mut self, user_id: Snowflake) -> Result<User> {
+        self.send_command(ActivityCmd::GetUser, GetUserArgs { id: user_id })
+    }
+
+    /// Gets the relationships of the user: their friends, blocked users and
+    /// pending friend requests, with the presence of each user.
+    ///
+    /// Requires the [`RelationshipsRead`](Scope::RelationshipsRead) scope.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the command failed.
+    fn get_relationships(&mut self) -> Result<Vec<Relationship>> {
+        let response: RelationshipsResponse = self.send_command(ActivityCmd::GetRelationships, crate::Empty)?;
+
+        Ok(response.relationships)
     }
 
     /// Closes the Discord IPC connection. Implementation is dependent on platform.
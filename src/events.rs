@@ -0,0 +1,126 @@
+//! Provides typed representations of the events dispatched by Discord,
+//! received via [`DiscordIpc::recv_event`](crate::DiscordIpc::recv_event).
+use crate::{
+    models::{
+        ActivityInvite, ActivityJoinRequest, ActivitySecret, CaptureShortcutChange, Entitlement, Lobby, LobbyDelete, LobbyMemberEvent, LobbyMessage, MessageEvent, Notification, OverlayState, Relationship, Speaking,
+        UserAchievement, VoiceChannelSelect, VoiceConnectionStatus, VoiceSettings, VoiceState,
+    },
+    ActivityEvent, DiscordIPCCommandIncoming, Error,
+};
+
+/// An event dispatched by Discord.
+///
+/// Events must be subscribed to with [`DiscordIpc::subscribe`](crate::DiscordIpc::subscribe)
+/// before they are dispatched, with the exception of [`Ready`](ActivityEvent::Ready)
+/// and [`Error`](ActivityEvent::Error).
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Debug)]
+pub enum Event {
+    /// The user joined or left a voice channel.
+    VoiceChannelSelect(VoiceChannelSelect),
+    /// A user joined the subscribed voice channel.
+    VoiceStateCreate(VoiceState),
+    /// The voice state of a user in the subscribed voice channel changed.
+    VoiceStateUpdate(VoiceState),
+    /// A user left the subscribed voice channel.
+    VoiceStateDelete(VoiceState),
+    /// A user in the subscribed voice channel started speaking.
+    SpeakingStart(Speaking),
+    /// A user in the subscribed voice channel stopped speaking.
+    SpeakingStop(Speaking),
+    /// The voice connection of the user changed.
+    VoiceConnectionStatus(VoiceConnectionStatus),
+    /// The voice settings of the user changed.
+    VoiceSettingsUpdate(Box<VoiceSettings>),
+    /// The user joined an activity, from an invite or from its join button.
+    /// Contains the join secret of the activity.
+    ActivityJoin(ActivitySecret),
+    /// The user started spectating an activity. Contains the spectate
+    /// secret of the activity.
+    ActivitySpectate(ActivitySecret),
+    /// Another user asked to join the activity of the user.
+    ActivityJoinRequest(ActivityJoinRequest),
+    /// The user was invited to an activity.
+    ActivityInvite(Box<ActivityInvite>),
+    /// The subscribed lobby was updated.
+    LobbyUpdate(Box<Lobby>),
+    /// The subscribed lobby was deleted.
+    LobbyDelete(LobbyDelete),
+    /// A member joined the subscribed lobby.
+    LobbyMemberConnect(LobbyMemberEvent),
+    /// A member left the subscribed lobby.
+    LobbyMemberDisconnect(LobbyMemberEvent),
+    /// The metadata of a member of the subscribed lobby changed.
+    LobbyMemberUpdate(LobbyMemberEvent),
+    /// A member sent a message to the subscribed lobby.
+    LobbyMessage(LobbyMessage),
+    /// A message was sent in the subscribed channel.
+    MessageCreate(Box<MessageEvent>),
+    /// A message of the subscribed channel was edited.
+    MessageUpdate(Box<MessageEvent>),
+    /// A message of the subscribed channel was deleted.
+    MessageDelete(Box<MessageEvent>),
+    /// Discord showed a notification to the user.
+    NotificationCreate(Box<Notification>),
+    /// The user pressed or released a key while a shortcut is captured.
+    CaptureShortcutChange(CaptureShortcutChange),
+    /// The user was granted an entitlement, such as by purchasing a SKU.
+    EntitlementCreate(Entitlement),
+    /// An entitlement of the user was revoked.
+    EntitlementDelete(Entitlement),
+    /// The progress of the user towards an achievement changed.
+    UserAchievementUpdate(UserAchievement),
+    /// The state of the overlay, dispatched when subscribing to it.
+    Overlay(OverlayState),
+    /// The overlay was enabled, disabled, locked or unlocked.
+    OverlayUpdate(OverlayState),
+    /// A relationship of the user, or the presence of the other user, changed.
+    RelationshipUpdate(Box<Relationship>),
+    /// An event that has no typed representation.
+    Other(DiscordIPCCommandIncoming),
+}
+
+impl TryFrom<DiscordIPCCommandIncoming> for Event {
+    type Error = Error;
+
+    fn try_from(incoming: DiscordIPCCommandIncoming) -> Result<Self, Self::Error> {
+        use serde_json::from_value;
+
+        let Some(evt) = incoming.evt else {
+            return Ok(Event::Other(incoming));
+        };
+
+        Ok(match evt {
+            ActivityEvent::VoiceChannelSelect => Event::VoiceChannelSelect(from_value(incoming.data)?),
+            ActivityEvent::VoiceStateCreate => Event::VoiceStateCreate(from_value(incoming.data)?),
+            ActivityEvent::VoiceStateUpdate => Event::VoiceStateUpdate(from_value(incoming.data)?),
+            ActivityEvent::VoiceStateDelete => Event::VoiceStateDelete(from_value(incoming.data)?),
+            ActivityEvent::SpeakingStart => Event::SpeakingStart(from_value(incoming.data)?),
+            ActivityEvent::SpeakingStop => Event::SpeakingStop(from_value(incoming.data)?),
+            ActivityEvent::VoiceConnectionStatus => Event::VoiceConnectionStatus(from_value(incoming.data)?),
+            ActivityEvent::VoiceSettingsUpdate => Event::VoiceSettingsUpdate(from_value(incoming.data)?),
+            ActivityEvent::ActivityJoin => Event::ActivityJoin(from_value(incoming.data)?),
+            ActivityEvent::ActivitySpectate => Event::ActivitySpectate(from_value(incoming.data)?),
+            ActivityEvent::ActivityJoinRequest => Event::ActivityJoinRequest(from_value(incoming.data)?),
+            ActivityEvent::ActivityInvite => Event::ActivityInvite(from_value(incoming.data)?),
+            ActivityEvent::LobbyUpdate => Event::LobbyUpdate(from_value(incoming.data)?),
+            ActivityEvent::LobbyDelete => Event::LobbyDelete(from_value(incoming.data)?),
+            ActivityEvent::LobbyMemberConnect => Event::LobbyMemberConnect(from_value(incoming.data)?),
+            ActivityEvent::LobbyMemberDisconnect => Event::LobbyMemberDisconnect(from_value(incoming.data)?),
+            ActivityEvent::LobbyMemberUpdate => Event::LobbyMemberUpdate(from_value(incoming.data)?),
+            ActivityEvent::LobbyMessage => Event::LobbyMessage(from_value(incoming.data)?),
+            ActivityEvent::MessageCreate => Event::MessageCreate(from_value(incoming.data)?),
+            ActivityEvent::MessageUpdate => Event::MessageUpdate(from_value(incoming.data)?),
+            ActivityEvent::MessageDelete => Event::MessageDelete(from_value(incoming.data)?),
+            ActivityEvent::NotificationCreate => Event::NotificationCreate(from_value(incoming.data)?),
+            ActivityEvent::CaptureShortcutChange => Event::CaptureShortcutChange(from_value(incoming.data)?),
+            ActivityEvent::EntitlementCreate => Event::EntitlementCreate(from_value(incoming.data)?),
+            ActivityEvent::EntitlementDelete => Event::EntitlementDelete(from_value(incoming.data)?),
+            ActivityEvent::UserAchievementUpdate => Event::UserAchievementUpdate(from_value(incoming.data)?),
+            ActivityEvent::Overlay => Event::Overlay(from_value(incoming.data)?),
+            ActivityEvent::OverlayUpdate => Event::OverlayUpdate(from_value(incoming.data)?),
+            ActivityEvent::RelationshipUpdate => Event::RelationshipUpdate(from_value(incoming.data)?),
+            _ => Event::Other(incoming),
+        })
+    }
+}
@@ -1,5 +1,5 @@
 use crate::Empty;
-use crate::{Error, Result, discord_ipc::DiscordIpc};
+use crate::{Result, discord_ipc::DiscordIpc, models::Snowflake, ClientState};
 use std::os::unix::net::UnixStream;
 use std::{
     env::var,
@@ -17,30 +17,35 @@ const ENV_KEYS: [&str; 4] = ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"];
 /// underlying [`DiscordIpc`](trait@DiscordIpc) trait.
 pub struct DiscordIpcClient {
     /// Client ID of the IPC client.
-    pub client_id: String,
+    pub client_id: Snowflake,
     connected: bool,
+    state: ClientState,
     socket: Option<UnixStream>,
 }
 
 impl DiscordIpcClient {
     /// Creates a new `DiscordIpcClient`.
     ///
+    /// # Errors
+    /// Returns an `Err` variant if the client ID is not a valid snowflake.
+    ///
     /// # Examples
     /// ```
-    /// let ipc_client = DiscordIpcClient::new("<some client id>");
+    /// let ipc_client = DiscordIpcClient::new("<some client id>")?;
     /// ```
-    pub fn new(client_id: &str) -> Self {
-        Self {
-            client_id: client_id.to_string(),
+    pub fn new(client_id: &str) -> Result<Self> {
+        Ok(Self {
+            client_id: client_id.parse()?,
             connected: false,
+            state: ClientState::default(),
             socket: None,
-        }
+        })
     }
 
     fn get_pipe_pattern() -> PathBuf {
         ENV_KEYS
             .iter()
-            .find_map(|key| var(key).ok().map(|val| PathBuf::from(val)))
+            .find_map(|key| var(key).ok().map(PathBuf::from))
             .unwrap_or("/tmp/".into())
     }
 }
@@ -83,7 +88,7 @@ impl DiscordIpc for DiscordIpcClient {
     }
 
     fn close(&mut self) -> Result<()> {
-        if self.send(&Empty, 2).is_ok() {}
+        let _ = self.send(&Empty, 2);
 
         let socket = self.socket.as_mut().unwrap();
 
@@ -95,7 +100,15 @@ impl DiscordIpc for DiscordIpcClient {
         Ok(())
     }
 
-    fn get_client_id(&self) -> &String {
+    fn get_client_id(&self) -> &Snowflake {
         &self.client_id
     }
+
+    fn get_state(&self) -> &ClientState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ClientState {
+        &mut self.state
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{Result, discord_ipc::DiscordIpc, Empty};
+use crate::{Result, discord_ipc::DiscordIpc, models::Snowflake, ClientState, Empty};
 use std::{
     fs::{File, OpenOptions},
     io::{Read, Write},
@@ -11,24 +11,29 @@ use std::{
 /// underlying [`DiscordIpc`](trait@DiscordIpc) trait.
 pub struct DiscordIpcClient {
     /// Client ID of the IPC client.
-    pub client_id: String,
+    pub client_id: Snowflake,
     connected: bool,
+    state: ClientState,
     socket: Option<File>,
 }
 
 impl DiscordIpcClient {
     /// Creates a new `DiscordIpcClient`.
     ///
+    /// # Errors
+    /// Returns an `Err` variant if the client ID is not a valid snowflake.
+    ///
     /// # Examples
     /// ```
-    /// let ipc_client = DiscordIpcClient::new("<some client id>");
+    /// let ipc_client = DiscordIpcClient::new("<some client id>")?;
     /// ```
-    pub fn new(client_id: &str) -> Self {
-        Self {
-            client_id: client_id.to_string(),
+    pub fn new(client_id: &str) -> Result<Self> {
+        Ok(Self {
+            client_id: client_id.parse()?,
             connected: false,
+            state: ClientState::default(),
             socket: None,
-        }
+        })
     }
 }
 
@@ -69,7 +74,7 @@ impl DiscordIpc for DiscordIpcClient {
     }
 
     fn close(&mut self) -> Result<()> {
-        if self.send(&Empty, 2).is_ok() {}
+        let _ = self.send(&Empty, 2);
 
         let socket = self.socket.as_mut().unwrap();
         socket.flush()?;
@@ -79,7 +84,15 @@ impl DiscordIpc for DiscordIpcClient {
         Ok(())
     }
 
-    fn get_client_id(&self) -> &String {
+    fn get_client_id(&self) -> &Snowflake {
         &self.client_id
     }
+
+    fn get_state(&self) -> &ClientState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ClientState {
+        &mut self.state
+    }
 }
@@ -10,7 +10,7 @@
 //! use discord_ipc::{activity, DiscordIpc, DiscordIpcClient};
 //!
 //! fn main() -> Result<(), Box<dyn std::error::Error>> {
-//!     let mut client = DiscordIpcClient::new("<some client id>");
+//!     let mut client = DiscordIpcClient::new("<some client id>")?;
 //!     client.connect()?;
 //!
 //!     let payload = activity::Activity::new().state("Hello world!");
@@ -21,8 +21,20 @@
 
 mod discord_ipc;
 mod pack_unpack;
+mod state;
+mod subscription;
 pub use discord_ipc::*;
+pub use state::ClientState;
+pub mod achievements;
 pub mod activity;
+pub mod events;
+pub mod messages;
+pub mod models;
+pub mod notifications;
+pub mod oauth2;
+pub mod relationships;
+pub mod secret;
+pub mod voice;
 
 #[cfg(unix)]
 mod ipc_unix;
@@ -43,9 +55,9 @@ use serde::ser::SerializeStruct;
 ///
 /// # Examples
 /// ```
-/// let ipc_client = discord_ipc_client::new_client("<some client id>");
+/// let ipc_client = discord_ipc_client::new_client("<some client id>")?;
 /// ```
-pub fn new_client(client_id: &str) -> impl DiscordIpc {
+pub fn new_client(client_id: &str) -> Result<impl DiscordIpc> {
     ipc::DiscordIpcClient::new(client_id)
 }
 
@@ -70,11 +82,114 @@ pub enum Error {
     /// Could not connect to the Discord IPC socket.
     #[error("could not connect to the Discord IPC socket: {0}")]
     CouldNotConnect(std::io::Error),
+    /// Discord responded to a command with an error.
+    #[error(transparent)]
+    Discord(DiscordIPCError),
+    /// Discord responded to a command with an error code not known to this crate.
+    #[error("Discord IPC error [{code}]: {message}")]
+    UnknownDiscordError {
+        /// The error code.
+        code: u16,
+        /// A human readable message.
+        message: String,
+    },
+    /// Selecting a channel timed out.
+    #[error("selecting the channel timed out")]
+    SelectChannelTimedOut,
+    /// The user is already in a voice channel, and `force` was not set.
+    #[error("the user is already in a voice channel, selecting another one requires force")]
+    SelectVoiceForceRequired,
+    /// The lobby does not exist, or the user is not a member of it.
+    #[error("invalid lobby")]
+    InvalidLobby,
+    /// The secret of the lobby is invalid.
+    #[error("invalid lobby secret")]
+    InvalidLobbySecret,
+    /// The lobby is full.
+    #[error("the lobby is full")]
+    LobbyFull,
+    /// A shortcut is already being captured.
+    #[error("a shortcut is already being captured")]
+    CaptureShortcutAlreadyListening,
+    /// The user canceled a purchase.
+    #[error("the purchase was canceled")]
+    PurchaseCanceled,
+    /// A purchase failed.
+    #[error("the purchase failed")]
+    PurchaseFailed,
+    /// The entitlement does not exist, or does not belong to the user.
+    #[error("invalid entitlement")]
+    InvalidEntitlement,
+    /// The application is not allowed to update the achievements of the user.
+    #[error("unauthorized to update the achievement")]
+    UnauthorizedForAchievement,
+    /// An OAuth2 token exchange requires a [`TokenExchanger`](oauth2::TokenExchanger),
+    /// but none has been set.
+    #[error("no token exchanger has been set")]
+    MissingTokenExchanger,
+    /// The access token could not be refreshed, as there is no refresh token.
+    #[error("no refresh token is available")]
+    MissingRefreshToken,
+    /// A [`TokenExchanger`](oauth2::TokenExchanger) failed to exchange a code or refresh token.
+    #[error("token exchange failed: {0}")]
+    TokenExchange(Box<dyn std::error::Error + Send + Sync>),
+    /// An activity secret is malformed, or its signature is invalid.
+    #[error("invalid activity secret")]
+    InvalidSecret,
+    /// An activity secret has expired.
+    #[error("activity secret has expired")]
+    SecretExpired,
+    /// An activity secret is longer than Discord allows.
+    #[error("activity secret is {0} characters long, the maximum is 128")]
+    SecretTooLong(usize),
+    /// A [`NotificationSink`](notifications::NotificationSink) failed to show a notification.
+    #[error("failed to show notification: {0}")]
+    Notification(Box<dyn std::error::Error + Send + Sync>),
+    /// A [`SignedTicket`](models::SignedTicket) is malformed.
+    #[error("invalid signed ticket")]
+    InvalidTicket,
+    /// An image size is not a power of two between 16 and 1024.
+    #[error("invalid image size {0}, must be a power of two between 16 and 1024")]
+    InvalidImageSize(u16),
+    /// An image returned by Discord is not a base64 `data:` URI.
+    #[error("invalid image data")]
+    InvalidImage,
+    /// A string could not be parsed as a [`Snowflake`](models::Snowflake).
+    #[error("invalid snowflake: {0:?}")]
+    InvalidSnowflake(String),
+}
+
+impl From<DiscordIPCError> for Error {
+    fn from(error: DiscordIPCError) -> Self {
+        use DiscordIPCErrorCodeNonCritical::*;
+
+        match error.code {
+            DiscordIPCErrorCode::NonCritical(SelectChannelTimedOut) => Error::SelectChannelTimedOut,
+            DiscordIPCErrorCode::NonCritical(SelectVoiceForceRequired) => Error::SelectVoiceForceRequired,
+            DiscordIPCErrorCode::NonCritical(InvalidLobby) => Error::InvalidLobby,
+            DiscordIPCErrorCode::NonCritical(InvalidLobbySecret) => Error::InvalidLobbySecret,
+            DiscordIPCErrorCode::NonCritical(LobbyFull) => Error::LobbyFull,
+            DiscordIPCErrorCode::NonCritical(CaptureShortcutAlreadyListening) => Error::CaptureShortcutAlreadyListening,
+            DiscordIPCErrorCode::NonCritical(PurchaseCanceled) => Error::PurchaseCanceled,
+            DiscordIPCErrorCode::NonCritical(PurchaseError) => Error::PurchaseFailed,
+            DiscordIPCErrorCode::NonCritical(InvalidEntitlement) => Error::InvalidEntitlement,
+            DiscordIPCErrorCode::NonCritical(UnauthorizedForAchievement) => Error::UnauthorizedForAchievement,
+            _ => Error::Discord(error),
+        }
+    }
 }
 
 /// The result type for this crate.
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// The current time, in seconds since the Unix epoch.
+pub(crate) fn unix_time() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
 /// An empty struct that represents the absence of a payload.
 pub(crate) struct Empty;
 impl serde::Serialize for Empty {
@@ -0,0 +1,180 @@
+//! Provides a stream of the messages of a text channel, keeping its
+//! recent history in sync with the message events dispatched by Discord.
+use std::collections::VecDeque;
+
+use crate::{
+    events::Event,
+    models::{ChannelArgs, Message, MessageEvent, Snowflake},
+    ActivityEvent, DiscordIpc, Result,
+};
+
+/// The events of a text channel, subscribed to by a [`MessageStream`].
+const MESSAGE_EVENTS: [ActivityEvent; 3] = [ActivityEvent::MessageCreate, ActivityEvent::MessageUpdate, ActivityEvent::MessageDelete];
+
+/// A stream of the messages of a text channel.
+///
+/// The stream subscribes to the message events of the channel, and keeps
+/// its latest messages ordered from oldest to newest, applying edits and
+/// deletions as they are received. It unsubscribes when it is closed with
+/// [`close`](MessageStream::close), which waits for Discord to confirm. When
+/// the stream is dropped instead, it unsubscribes without waiting for the
+/// responses, and errors are only logged.
+///
+/// Requires the [`Rpc`](crate::models::Scope::Rpc) and
+/// [`MessagesRead`](crate::models::Scope::MessagesRead) scopes.
+///
+/// # Examples
+/// ```no_run
+/// # use discord_ipc_rp::{events::Event, messages::MessageStream, models::Snowflake, DiscordIpcClient};
+/// # fn main() -> discord_ipc_rp::Result<()> {
+/// # let channel_id = Snowflake::new(199737254929760256);
+/// # let mut client = DiscordIpcClient::new("771124766517755954")?;
+/// let mut stream = MessageStream::subscribe(&mut client, channel_id, 50)?;
+/// loop {
+///     if let Event::MessageCreate(_) | Event::MessageUpdate(_) | Event::MessageDelete(_) = stream.recv()? {
+///         for message in stream.history() {
+///             println!("{}", message.content);
+///         }
+///     }
+/// }
+/// # }
+/// ```
+pub struct MessageStream<'a, C: DiscordIpc> {
+    client: &'a mut C,
+    channel_id: Snowflake,
+    history: VecDeque<Message>,
+    capacity: usize,
+    subscribed: bool,
+}
+
+impl<'a, C: DiscordIpc> MessageStream<'a, C> {
+    /// Creates a new `MessageStream` keeping the latest `capacity` messages
+    /// of a channel, subscribing to its message events and loading its
+    /// latest messages.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if subscribing or loading the channel failed.
+    pub fn subscribe(client: &'a mut C, channel_id: Snowflake, capacity: usize) -> Result<Self> {
+        let mut stream = Self {
+            client,
+            channel_id,
+            history: VecDeque::with_capacity(capacity),
+            capacity,
+            subscribed: false,
+        };
+
+        for evt in MESSAGE_EVENTS {
+            stream.client.subscribe(evt, ChannelArgs { channel_id })?;
+            stream.subscribed = true;
+        }
+        for message in stream.client.get_channel(channel_id)?.messages {
+            stream.insert(message);
+        }
+
+        Ok(stream)
+    }
+
+    /// Receives the next event from the client, updating the history if it
+    /// is a message event of the channel. Every event is returned, so that
+    /// other events are not lost while the stream borrows the client.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if receiving the event failed.
+    pub fn recv(&mut self) -> Result<Event> {
+        let event = self.client.recv_event()?;
+        self.handle_event(&event);
+
+        Ok(event)
+    }
+
+    /// Updates the history from an event, and returns whether it changed.
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::MessageCreate(created) if self.is_own(created) => {
+                self.insert(created.message.clone());
+            }
+            Event::MessageUpdate(updated) if self.is_own(updated) => match self.position(updated.message.id) {
+                Ok(index) => self.history[index] = updated.message.clone(),
+                Err(_) => return false,
+            },
+            Event::MessageDelete(deleted) if self.is_own(deleted) => match self.position(deleted.message.id) {
+                Ok(index) => {
+                    self.history.remove(index);
+                }
+                Err(_) => return false,
+            },
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// The ID of the channel.
+    pub fn channel_id(&self) -> Snowflake {
+        self.channel_id
+    }
+
+    /// The latest messages of the channel, from oldest to newest.
+    pub fn history(&self) -> impl Iterator<Item = &Message> {
+        self.history.iter()
+    }
+
+    /// A message of the history.
+    pub fn message(&self, id: Snowflake) -> Option<&Message> {
+        self.position(id).ok().map(|index| &self.history[index])
+    }
+
+    /// The client of the stream, to send commands while the stream is open.
+    pub fn client(&mut self) -> &mut C {
+        self.client
+    }
+
+    /// Unsubscribes from the message events of the channel.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if unsubscribing failed.
+    pub fn close(mut self) -> Result<()> {
+        self.unsubscribe()
+    }
+
+    fn unsubscribe(&mut self) -> Result<()> {
+        if std::mem::take(&mut self.subscribed) {
+            for evt in MESSAGE_EVENTS {
+                self.client.unsubscribe(evt, ChannelArgs { channel_id: self.channel_id })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_own(&self, event: &MessageEvent) -> bool {
+        event.channel_id == self.channel_id
+    }
+
+    /// Searches the history, which is sorted by ID and thus by creation time.
+    fn position(&self, id: Snowflake) -> std::result::Result<usize, usize> {
+        self.history.binary_search_by_key(&id, |message| message.id)
+    }
+
+    fn insert(&mut self, message: Message) {
+        match self.position(message.id) {
+            Ok(index) => self.history[index] = message,
+            Err(index) => self.history.insert(index, message),
+        }
+        while self.history.len() > self.capacity {
+            self.history.pop_front();
+        }
+    }
+}
+
+impl<C: DiscordIpc> Drop for MessageStream<'_, C> {
+    fn drop(&mut self) {
+        if std::mem::take(&mut self.subscribed) {
+            for evt in MESSAGE_EVENTS {
+                if let Err(err) = self.client.unsubscribe_without_reply(evt, ChannelArgs { channel_id: self.channel_id }) {
+                    log::warn!("failed to unsubscribe from the messages of channel {}: {}", self.channel_id, err);
+                }
+            }
+        }
+    }
+}
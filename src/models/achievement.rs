@@ -0,0 +1,58 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::Snowflake;
+
+/// The progress of the user towards an achievement of the application.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct UserAchievement {
+    /// The ID of the user.
+    pub user_id: Snowflake,
+    /// The ID of the achievement.
+    pub achievement_id: Snowflake,
+    /// How complete the achievement is, from `0` to `100`.
+    pub percent_complete: u8,
+    /// When the achievement was unlocked, as an ISO 8601 timestamp.
+    pub unlocked_at: Option<String>,
+    /// The metadata of the progress.
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// The progress of the user towards an achievement, to set with
+/// [`DiscordIpc::set_user_achievement`](crate::DiscordIpc::set_user_achievement).
+///
+/// Note that all methods return `Self`, and can be chained
+/// for fluency
+#[derive(Serialize, Clone, Default, Eq, PartialEq, Hash, Debug)]
+pub struct AchievementProgress {
+    percent_complete: u8,
+
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    metadata: BTreeMap<String, String>,
+}
+
+impl AchievementProgress {
+    /// Creates a new `AchievementProgress`, clamping `percent_complete` to `100`
+    pub fn new(percent_complete: u8) -> Self {
+        Self {
+            percent_complete: percent_complete.min(100),
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    /// Sets a metadata entry of the progress
+    pub fn metadata(mut self, key: &str, value: &str) -> Self {
+        self.metadata.insert(key.to_string(), value.to_string());
+        self
+    }
+}
+
+/// The arguments of a [`SetUserAchievement`](crate::ActivityCmd::SetUserAchievement) command.
+#[derive(Serialize, Debug)]
+pub(crate) struct SetUserAchievementArgs<'a> {
+    pub achievement_id: Snowflake,
+    #[serde(flatten)]
+    pub progress: &'a AchievementProgress,
+}
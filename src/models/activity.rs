@@ -0,0 +1,166 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use strum::FromRepr;
+
+use super::{Snowflake, User};
+
+/// The type of a [`UserActivity`].
+#[allow(missing_docs)]
+#[derive(FromRepr, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[repr(u8)]
+pub enum ActivityType {
+    Playing = 0,
+    Streaming = 1,
+    Listening = 2,
+    Watching = 3,
+    Custom = 4,
+    Competing = 5,
+    /// An activity type not known to this crate.
+    Unknown = u8::MAX,
+}
+impl Serialize for ActivityType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+impl<'de> Deserialize<'de> for ActivityType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let kind = u8::deserialize(deserializer)?;
+
+        Ok(ActivityType::from_repr(kind).unwrap_or(ActivityType::Unknown))
+    }
+}
+
+/// An activity of a user, as received from Discord.
+///
+/// To set the activity of the current user, see [`activity::Activity`](crate::activity::Activity).
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct UserActivity {
+    /// The name of the activity, such as the name of the game.
+    #[serde(default)]
+    pub name: String,
+    /// The type of the activity.
+    #[serde(rename = "type")]
+    pub kind: ActivityType,
+    /// The ID of the application of the activity, if any.
+    pub application_id: Option<Snowflake>,
+    /// The state of the activity.
+    pub state: Option<String>,
+    /// The details of the activity.
+    pub details: Option<String>,
+    /// The start and end of the activity.
+    pub timestamps: Option<ActivityTimestamps>,
+    /// The party of the activity.
+    pub party: Option<ActivityParty>,
+    /// The images of the activity and their hover texts.
+    pub assets: Option<ActivityAssets>,
+    /// The ID of the session the activity belongs to.
+    pub session_id: Option<String>,
+}
+
+/// The start and end of a [`UserActivity`], in milliseconds since the Unix epoch.
+#[allow(missing_docs)]
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ActivityTimestamps {
+    pub start: Option<i64>,
+    pub end: Option<i64>,
+}
+
+/// The party of a [`UserActivity`].
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ActivityParty {
+    /// The ID of the party.
+    pub id: Option<String>,
+    /// The current and maximum size of the party.
+    pub size: Option<[u32; 2]>,
+}
+
+/// The images of a [`UserActivity`] and their hover texts.
+#[allow(missing_docs)]
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ActivityAssets {
+    pub large_image: Option<String>,
+    pub large_text: Option<String>,
+    pub small_image: Option<String>,
+    pub small_text: Option<String>,
+}
+
+/// The action an activity invite is for.
+#[allow(missing_docs)]
+#[derive(FromRepr, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[repr(u8)]
+pub enum ActivityActionType {
+    Join = 1,
+    Spectate = 2,
+    /// An activity action type not known to this crate.
+    Unknown = u8::MAX,
+}
+impl Serialize for ActivityActionType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+impl<'de> Deserialize<'de> for ActivityActionType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let kind = u8::deserialize(deserializer)?;
+
+        Ok(ActivityActionType::from_repr(kind).unwrap_or(ActivityActionType::Unknown))
+    }
+}
+
+/// The data of the [`ActivityJoin`](crate::ActivityEvent::ActivityJoin) and
+/// [`ActivitySpectate`](crate::ActivityEvent::ActivitySpectate) events.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ActivitySecret {
+    /// The join or spectate secret of the activity that was joined.
+    pub secret: String,
+}
+
+/// The data of an [`ActivityJoinRequest`](crate::ActivityEvent::ActivityJoinRequest) event.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ActivityJoinRequest {
+    /// The user that asked to join the activity.
+    pub user: User,
+}
+
+/// The data of an [`ActivityInvite`](crate::ActivityEvent::ActivityInvite) event.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ActivityInvite {
+    /// What the user was invited to do.
+    #[serde(rename = "type")]
+    pub kind: ActivityActionType,
+    /// The user that sent the invite.
+    pub user: User,
+    /// The activity the user was invited to.
+    pub activity: UserActivity,
+    /// The ID of the channel the invite was sent in.
+    pub channel_id: Snowflake,
+    /// The ID of the message of the invite.
+    pub message_id: Snowflake,
+}
+
+/// The arguments of commands targeting a user.
+#[derive(Serialize, Debug)]
+pub(crate) struct UserArgs {
+    pub user_id: Snowflake,
+}
+
+/// The arguments of an [`ActivityInviteUser`](crate::ActivityCmd::ActivityInviteUser) command.
+#[derive(Serialize, Debug)]
+pub(crate) struct ActivityInviteUserArgs<'a> {
+    pub user_id: Snowflake,
+    #[serde(rename = "type")]
+    pub kind: ActivityActionType,
+    pub content: &'a str,
+    pub pid: u32,
+}
+
+/// The arguments of an [`AcceptActivityInvite`](crate::ActivityCmd::AcceptActivityInvite) command.
+#[derive(Serialize, Debug)]
+pub(crate) struct AcceptActivityInviteArgs<'a> {
+    #[serde(rename = "type")]
+    pub kind: ActivityActionType,
+    pub user_id: Snowflake,
+    pub session_id: Option<&'a str>,
+    pub channel_id: Snowflake,
+    pub message_id: Snowflake,
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+use super::Snowflake;
+
+/// A Discord application.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Application {
+    /// The ID of the application.
+    pub id: Snowflake,
+    /// The name of the application.
+    pub name: String,
+    /// The description of the application.
+    #[serde(default)]
+    pub description: String,
+    /// The icon hash of the application, if set.
+    pub icon: Option<String>,
+    /// The RPC origins allowed for the application.
+    #[serde(default)]
+    pub rpc_origins: Vec<String>,
+}
@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use strum::FromRepr;
+
+use super::{Guild, Message, Snowflake, VoiceState};
+
+/// The type of a [`Channel`].
+#[allow(missing_docs)]
+#[derive(FromRepr, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[repr(u8)]
+pub enum ChannelType {
+    GuildText = 0,
+    Dm = 1,
+    GuildVoice = 2,
+    GroupDm = 3,
+    GuildCategory = 4,
+    GuildAnnouncement = 5,
+    AnnouncementThread = 10,
+    PublicThread = 11,
+    PrivateThread = 12,
+    GuildStageVoice = 13,
+    GuildDirectory = 14,
+    GuildForum = 15,
+    GuildMedia = 16,
+    /// A channel type not known to this crate.
+    Unknown = u8::MAX,
+}
+impl Serialize for ChannelType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+impl<'de> Deserialize<'de> for ChannelType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let kind = u8::deserialize(deserializer)?;
+
+        Ok(ChannelType::from_repr(kind).unwrap_or(ChannelType::Unknown))
+    }
+}
+
+/// A Discord channel.
+///
+/// Channels returned by [`DiscordIpc::get_channels`](crate::DiscordIpc::get_channels)
+/// only have their `id`, `name` and `kind` set.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Channel {
+    /// The ID of the channel.
+    pub id: Snowflake,
+    /// The ID of the guild the channel is in, if any.
+    pub guild_id: Option<Snowflake>,
+    /// The name of the channel.
+    pub name: String,
+    /// The type of the channel.
+    #[serde(rename = "type")]
+    pub kind: ChannelType,
+    /// The topic of the channel, for text channels.
+    pub topic: Option<String>,
+    /// The bitrate of the channel, for voice channels.
+    pub bitrate: Option<u32>,
+    /// The user limit of the channel, for voice channels, `0` if there is none.
+    pub user_limit: Option<u32>,
+    /// The sorting position of the channel.
+    pub position: Option<i32>,
+    /// The voice states of the users in the channel, for voice channels.
+    #[serde(default)]
+    pub voice_states: Vec<VoiceState>,
+    /// The latest messages of the channel, from oldest to newest, for text channels.
+    #[serde(default)]
+    pub messages: Vec<Message>,
+}
+
+/// An invite to a channel.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Invite {
+    /// The invite code, as used in `https://discord.gg/<code>`.
+    pub code: String,
+    /// The guild the invite is for.
+    pub guild: Option<Guild>,
+    /// The number of times the invite has been used.
+    #[serde(default)]
+    pub uses: u32,
+    /// The maximum number of times the invite can be used, `0` if unlimited.
+    #[serde(default)]
+    pub max_uses: u32,
+    /// The number of seconds the invite is valid for, `0` if it never expires.
+    #[serde(default)]
+    pub max_age: u32,
+    /// Whether the invite only grants temporary membership.
+    #[serde(default)]
+    pub temporary: bool,
+    /// When the invite was created, as an ISO 8601 timestamp.
+    pub created_at: Option<String>,
+}
+
+/// The arguments of commands targeting a channel.
+#[derive(Serialize, Debug)]
+pub(crate) struct ChannelArgs {
+    pub channel_id: Snowflake,
+}
+
+/// The arguments of the [`SelectVoiceChannel`](crate::ActivityCmd::SelectVoiceChannel)
+/// and [`SelectTextChannel`](crate::ActivityCmd::SelectTextChannel) commands.
+#[derive(Serialize, Debug)]
+pub(crate) struct SelectChannelArgs {
+    pub channel_id: Option<Snowflake>,
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "serialize_timeout")]
+    pub timeout: Option<Duration>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub force: bool,
+}
+
+/// Serializes a timeout in whole seconds, rounding up so that a non-zero
+/// timeout is never sent as 0.
+fn serialize_timeout<S: Serializer>(timeout: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+    timeout.map(|timeout| timeout.as_secs_f64().ceil() as u64).serialize(serializer)
+}
+
+/// The response to a [`GetChannels`](crate::ActivityCmd::GetChannels) command.
+#[derive(Deserialize, Debug)]
+pub(crate) struct ChannelsResponse {
+    pub channels: Vec<Channel>,
+}
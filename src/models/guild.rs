@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+use super::Snowflake;
+
+/// A Discord guild (server).
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Guild {
+    /// The ID of the guild.
+    pub id: Snowflake,
+    /// The name of the guild.
+    pub name: String,
+    /// The URL of the guild's icon, if set.
+    pub icon_url: Option<String>,
+}
+
+/// The arguments of commands targeting a guild.
+#[derive(Serialize, Debug)]
+pub(crate) struct GuildArgs {
+    pub guild_id: Snowflake,
+}
+
+/// The response to a [`GetGuilds`](crate::ActivityCmd::GetGuilds) command.
+#[derive(Deserialize, Debug)]
+pub(crate) struct GuildsResponse {
+    pub guilds: Vec<Guild>,
+}
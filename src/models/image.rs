@@ -0,0 +1,58 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+
+use super::Snowflake;
+use crate::Error;
+
+/// The format of an image fetched with
+/// [`DiscordIpc::get_user_avatar`](crate::DiscordIpc::get_user_avatar).
+#[allow(missing_docs)]
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageFormat {
+    Png,
+    Webp,
+    Jpg,
+    Gif,
+}
+
+/// An image fetched through the Discord client.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Image {
+    /// The MIME type of the image, such as `image/png`.
+    pub mime_type: String,
+    /// The content of the image.
+    pub data: Vec<u8>,
+}
+
+impl Image {
+    /// Decodes an image from a base64 `data:` URI.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidImage`] if the URI is not a base64 `data:` URI.
+    pub fn from_data_uri(uri: &str) -> Result<Self, Error> {
+        let (header, data) = uri.strip_prefix("data:").and_then(|uri| uri.split_once(',')).ok_or(Error::InvalidImage)?;
+        let mime_type = header.strip_suffix(";base64").ok_or(Error::InvalidImage)?;
+
+        Ok(Self {
+            mime_type: mime_type.to_string(),
+            data: STANDARD.decode(data).map_err(|_| Error::InvalidImage)?,
+        })
+    }
+}
+
+/// The arguments of a [`GetImage`](crate::ActivityCmd::GetImage) command.
+#[derive(Serialize, Debug)]
+pub(crate) struct GetImageArgs {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub id: Snowflake,
+    pub format: ImageFormat,
+    pub size: u16,
+}
+
+/// The response of a [`GetImage`](crate::ActivityCmd::GetImage) command.
+#[derive(Deserialize, Debug)]
+pub(crate) struct GetImageResponse {
+    pub data_url: String,
+}
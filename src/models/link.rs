@@ -0,0 +1,65 @@
+use serde::Serialize;
+
+use super::Snowflake;
+
+/// A location in the Discord client to open with
+/// [`DiscordIpc::deep_link`](crate::DiscordIpc::deep_link).
+#[derive(Serialize, Clone, Eq, PartialEq, Hash, Debug)]
+#[serde(tag = "type", content = "params", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DeepLink {
+    /// The settings of the user, optionally at a section such as `voice`.
+    UserSettings {
+        /// The section of the settings.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        section: Option<String>,
+    },
+    /// The changelog of the Discord client.
+    Changelog,
+    /// The game library of the user.
+    Library,
+    /// The home page of the store.
+    StoreHome,
+    /// The store page of a SKU.
+    #[serde(rename_all = "camelCase")]
+    StoreListing {
+        /// The ID of the SKU.
+        sku_id: Snowflake,
+    },
+    /// A prompt to pick one of the user's guilds, and open its settings.
+    PickGuildSettings {
+        /// The section of the settings.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        section: Option<String>,
+        /// The subsection of the settings.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        subsection: Option<String>,
+    },
+    /// A channel, optionally scrolled to a message.
+    #[serde(rename_all = "camelCase")]
+    Channel {
+        /// The ID of the guild of the channel, `None` for direct messages.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        guild_id: Option<Snowflake>,
+        /// The ID of the channel.
+        channel_id: Snowflake,
+        /// The ID of the message to scroll to.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        message_id: Option<Snowflake>,
+    },
+}
+
+/// The arguments of the [`InviteBrowser`](crate::ActivityCmd::InviteBrowser),
+/// [`GuildTemplateBrowser`](crate::ActivityCmd::GuildTemplateBrowser) and
+/// [`GiftCodeBrowser`](crate::ActivityCmd::GiftCodeBrowser) commands.
+#[derive(Serialize, Debug)]
+pub(crate) struct CodeArgs<'a> {
+    pub code: &'a str,
+}
+
+/// The arguments of a [`BrowserHandoff`](crate::ActivityCmd::BrowserHandoff) command.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BrowserHandoffArgs<'a> {
+    pub handoff_token: &'a str,
+    pub fingerprint: &'a str,
+}
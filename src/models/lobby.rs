@@ -0,0 +1,358 @@
+use std::collections::BTreeMap;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use strum::FromRepr;
+
+use super::{Snowflake, User};
+
+/// Who can join a [`Lobby`].
+#[derive(FromRepr, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[repr(u8)]
+pub enum LobbyType {
+    /// Only users with the secret of the lobby can join.
+    Private = 1,
+    /// Any user can find and join the lobby.
+    Public = 2,
+}
+impl Serialize for LobbyType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+impl<'de> Deserialize<'de> for LobbyType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let kind = u8::deserialize(deserializer)?;
+
+        LobbyType::from_repr(kind).ok_or_else(|| serde::de::Error::custom(format!("invalid lobby type: {}", kind)))
+    }
+}
+
+/// A lobby of users of the application.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Lobby {
+    /// The ID of the lobby.
+    pub id: Snowflake,
+    /// Who can join the lobby.
+    #[serde(rename = "type")]
+    pub kind: LobbyType,
+    /// The ID of the user that owns the lobby.
+    pub owner_id: Snowflake,
+    /// The secret used to join the lobby.
+    pub secret: String,
+    /// The maximum number of members of the lobby.
+    pub capacity: u32,
+    /// Whether new members are prevented from joining the lobby.
+    pub locked: bool,
+    /// The metadata of the lobby.
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+    /// The members of the lobby.
+    #[serde(default)]
+    pub members: Vec<LobbyMember>,
+    /// The ID of the application the lobby belongs to.
+    pub application_id: Option<Snowflake>,
+}
+
+/// A member of a [`Lobby`].
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct LobbyMember {
+    /// The user.
+    pub user: User,
+    /// The metadata of the member.
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// The properties of a lobby to create or update. Only the properties
+/// that are set are changed when updating a lobby.
+///
+/// Note that all methods return `Self`, and can be chained
+/// for fluency
+#[derive(Serialize, Clone, Default, Eq, PartialEq, Hash, Debug)]
+pub struct LobbyOptions {
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    kind: Option<LobbyType>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owner_id: Option<Snowflake>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    capacity: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    locked: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<BTreeMap<String, String>>,
+}
+
+impl LobbyOptions {
+    /// Creates a new `LobbyOptions`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets who can join the lobby
+    pub fn kind(mut self, kind: LobbyType) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Transfers the ownership of the lobby to another member, only
+    /// when updating a lobby
+    pub fn owner(mut self, owner_id: Snowflake) -> Self {
+        self.owner_id = Some(owner_id);
+        self
+    }
+
+    /// Sets the maximum number of members of the lobby
+    pub fn capacity(mut self, capacity: u32) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    /// Sets whether new members are prevented from joining the lobby
+    pub fn locked(mut self, locked: bool) -> Self {
+        self.locked = Some(locked);
+        self
+    }
+
+    /// Sets a metadata entry of the lobby
+    pub fn metadata(mut self, key: &str, value: &str) -> Self {
+        self.metadata.get_or_insert_with(BTreeMap::new).insert(key.to_string(), value.to_string());
+        self
+    }
+}
+
+/// The arguments of an [`UpdateLobby`](crate::ActivityCmd::UpdateLobby) command.
+#[derive(Serialize, Debug)]
+pub(crate) struct UpdateLobbyArgs<'a> {
+    pub id: Snowflake,
+    #[serde(flatten)]
+    pub options: &'a LobbyOptions,
+}
+
+/// The arguments of commands targeting a lobby.
+#[derive(Serialize, Debug)]
+pub(crate) struct LobbyArgs {
+    pub id: Snowflake,
+}
+
+/// The arguments of a [`ConnectToLobby`](crate::ActivityCmd::ConnectToLobby) command.
+#[derive(Serialize, Debug)]
+pub(crate) struct ConnectToLobbyArgs<'a> {
+    pub id: Snowflake,
+    pub secret: &'a str,
+}
+
+/// The arguments of an [`UpdateLobbyMember`](crate::ActivityCmd::UpdateLobbyMember) command.
+#[derive(Serialize, Debug)]
+pub(crate) struct UpdateLobbyMemberArgs<'a> {
+    pub lobby_id: Snowflake,
+    pub user_id: Snowflake,
+    pub metadata: &'a BTreeMap<String, String>,
+}
+
+/// How a lobby metadata value is compared in a [`LobbySearchQuery`] filter.
+#[derive(FromRepr, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[repr(i8)]
+pub enum LobbySearchComparison {
+    /// The metadata value is less than or equal to the given value.
+    LessThanOrEqual = -2,
+    /// The metadata value is less than the given value.
+    LessThan = -1,
+    /// The metadata value is equal to the given value.
+    Equal = 0,
+    /// The metadata value is greater than the given value.
+    GreaterThan = 1,
+    /// The metadata value is greater than or equal to the given value.
+    GreaterThanOrEqual = 2,
+    /// The metadata value is not equal to the given value.
+    NotEqual = 3,
+}
+impl Serialize for LobbySearchComparison {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i8(*self as i8)
+    }
+}
+
+/// How a lobby metadata value is interpreted when compared in a [`LobbySearchQuery`].
+#[derive(FromRepr, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[repr(u8)]
+pub enum LobbySearchCast {
+    /// The values are compared as strings.
+    String = 1,
+    /// The values are compared as numbers.
+    Number = 2,
+}
+impl Serialize for LobbySearchCast {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+/// How far away lobbies may be, relative to the user, to be found by a [`LobbySearchQuery`].
+#[derive(FromRepr, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[repr(u8)]
+pub enum LobbySearchDistance {
+    /// Lobbies in the same region as the user.
+    Local = 0,
+    /// Lobbies in the same and adjacent regions as the user.
+    Default = 1,
+    /// Lobbies far away from the user.
+    Extended = 2,
+    /// Lobbies anywhere in the world.
+    Global = 3,
+}
+impl Serialize for LobbySearchDistance {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+#[derive(Serialize, Clone, Eq, PartialEq, Hash, Debug)]
+struct LobbySearchFilter {
+    key: String,
+    value: String,
+    comparison: LobbySearchComparison,
+    cast: LobbySearchCast,
+}
+
+#[derive(Serialize, Clone, Eq, PartialEq, Hash, Debug)]
+struct LobbySearchSort {
+    key: String,
+    value: String,
+    cast: LobbySearchCast,
+}
+
+/// A query searching the public lobbies of the application.
+///
+/// Keys passed to [`filter`](Self::filter) and [`sort`](Self::sort) are
+/// metadata keys of the lobbies, without the `metadata.` prefix.
+///
+/// Note that all methods return `Self`, and can be chained
+/// for fluency
+///
+/// # Examples
+/// ```
+/// use discord_ipc_rp::models::{LobbySearchCast, LobbySearchComparison, LobbySearchDistance, LobbySearchQuery};
+///
+/// let query = LobbySearchQuery::new()
+///     .filter("mode", LobbySearchComparison::Equal, "ranked", LobbySearchCast::String)
+///     .sort("rating", "1200", LobbySearchCast::Number)
+///     .limit(10)
+///     .distance(LobbySearchDistance::Default);
+/// ```
+#[derive(Serialize, Clone, Default, Eq, PartialEq, Hash, Debug)]
+pub struct LobbySearchQuery {
+    filter: Vec<LobbySearchFilter>,
+    sort: Vec<LobbySearchSort>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    distance: Option<LobbySearchDistance>,
+}
+
+impl LobbySearchQuery {
+    /// Creates a new `LobbySearchQuery`, matching every lobby
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only matches lobbies whose metadata `key` compares to `value`
+    pub fn filter(mut self, key: &str, comparison: LobbySearchComparison, value: &str, cast: LobbySearchCast) -> Self {
+        self.filter.push(LobbySearchFilter {
+            key: format!("metadata.{}", key),
+            value: value.to_string(),
+            comparison,
+            cast,
+        });
+        self
+    }
+
+    /// Sorts the lobbies by how close their metadata `key` is to `value`
+    pub fn sort(mut self, key: &str, value: &str, cast: LobbySearchCast) -> Self {
+        self.sort.push(LobbySearchSort {
+            key: format!("metadata.{}", key),
+            value: value.to_string(),
+            cast,
+        });
+        self
+    }
+
+    /// Sets the maximum number of lobbies to return
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Sets how far away the lobbies may be
+    pub fn distance(mut self, distance: LobbySearchDistance) -> Self {
+        self.distance = Some(distance);
+        self
+    }
+}
+
+/// The payload of a [`LobbyDelete`](crate::events::Event::LobbyDelete) event.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct LobbyDelete {
+    /// The ID of the deleted lobby.
+    pub id: Snowflake,
+    /// Why the lobby was deleted.
+    #[serde(default)]
+    pub reason: u32,
+}
+
+/// The payload of the events of a member of a lobby connecting, disconnecting
+/// or being updated.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct LobbyMemberEvent {
+    /// The ID of the lobby.
+    pub lobby_id: Snowflake,
+    /// The member.
+    pub member: LobbyMember,
+}
+
+/// A message sent to the members of a lobby.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct LobbyMessage {
+    /// The ID of the lobby.
+    pub lobby_id: Snowflake,
+    /// The ID of the member that sent the message.
+    pub sender_id: Snowflake,
+    /// The content of the message, transmitted as base64.
+    #[serde(with = "base64_data")]
+    pub data: Vec<u8>,
+}
+
+/// (De)serializes binary data as a base64 string.
+mod base64_data {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&STANDARD.encode(data))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let data = String::deserialize(deserializer)?;
+
+        STANDARD.decode(data).map_err(serde::de::Error::custom)
+    }
+}
+
+/// The arguments of a [`SendToLobby`](crate::ActivityCmd::SendToLobby) command.
+#[derive(Serialize, Debug)]
+pub(crate) struct SendToLobbyArgs<'a> {
+    pub lobby_id: Snowflake,
+    #[serde(with = "base64_data")]
+    pub data: &'a [u8],
+}
+
+/// The arguments of subscriptions to the events of a lobby.
+#[derive(Serialize, Debug)]
+pub(crate) struct LobbyEventArgs {
+    pub lobby_id: Snowflake,
+}
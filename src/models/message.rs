@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Snowflake, User};
+
+/// A message sent in a text channel.
+///
+/// The messages of [`MessageDelete`](crate::events::Event::MessageDelete)
+/// events only contain their [`id`](Message::id).
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Message {
+    /// The ID of the message.
+    pub id: Snowflake,
+    /// The author of the message.
+    pub author: Option<User>,
+    /// The nickname of the author in the guild of the channel.
+    pub nick: Option<String>,
+    /// The color of the name of the author, in the `#rrggbb` format.
+    pub author_color: Option<String>,
+    /// The content of the message.
+    #[serde(default)]
+    pub content: String,
+    /// When the message was sent, as an ISO 8601 timestamp.
+    pub timestamp: Option<String>,
+    /// When the message was last edited, as an ISO 8601 timestamp.
+    pub edited_timestamp: Option<String>,
+    /// Whether the message is a text-to-speech message.
+    #[serde(default)]
+    pub tts: bool,
+    /// Whether the message is pinned.
+    #[serde(default)]
+    pub pinned: bool,
+    /// The embeds of the message.
+    #[serde(default)]
+    pub embeds: Vec<Embed>,
+    /// The files attached to the message.
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+}
+
+/// Rich content embedded in a [`Message`].
+#[derive(Serialize, Deserialize, Clone, Default, Eq, PartialEq, Hash, Debug)]
+pub struct Embed {
+    /// The type of the embed, such as `rich`, `image` or `link`.
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    /// The title of the embed.
+    pub title: Option<String>,
+    /// The description of the embed.
+    pub description: Option<String>,
+    /// The URL of the title of the embed.
+    pub url: Option<String>,
+    /// The timestamp of the content of the embed, as an ISO 8601 timestamp.
+    pub timestamp: Option<String>,
+    /// The color of the border of the embed.
+    pub color: Option<u32>,
+    /// The footer of the embed.
+    pub footer: Option<EmbedFooter>,
+    /// The image of the embed.
+    pub image: Option<EmbedMedia>,
+    /// The thumbnail of the embed.
+    pub thumbnail: Option<EmbedMedia>,
+    /// The video of the embed.
+    pub video: Option<EmbedMedia>,
+    /// The author of the content of the embed.
+    pub author: Option<EmbedAuthor>,
+    /// The fields of the embed.
+    #[serde(default)]
+    pub fields: Vec<EmbedField>,
+}
+
+/// The footer of an [`Embed`].
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct EmbedFooter {
+    /// The text of the footer.
+    pub text: String,
+    /// The URL of the icon of the footer.
+    pub icon_url: Option<String>,
+}
+
+/// An image, thumbnail or video of an [`Embed`].
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct EmbedMedia {
+    /// The URL of the media.
+    pub url: Option<String>,
+    /// The width of the media, in pixels.
+    pub width: Option<u32>,
+    /// The height of the media, in pixels.
+    pub height: Option<u32>,
+}
+
+/// The author of the content of an [`Embed`].
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct EmbedAuthor {
+    /// The name of the author.
+    pub name: String,
+    /// The URL of the author.
+    pub url: Option<String>,
+    /// The URL of the icon of the author.
+    pub icon_url: Option<String>,
+}
+
+/// A field of an [`Embed`].
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct EmbedField {
+    /// The name of the field.
+    pub name: String,
+    /// The value of the field.
+    pub value: String,
+    /// Whether the field is displayed inline with other fields.
+    #[serde(default)]
+    pub inline: bool,
+}
+
+/// A file attached to a [`Message`].
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Attachment {
+    /// The ID of the attachment.
+    pub id: Snowflake,
+    /// The name of the file.
+    pub filename: String,
+    /// The size of the file, in bytes.
+    pub size: u64,
+    /// The URL of the file.
+    pub url: String,
+    /// The proxied URL of the file.
+    pub proxy_url: Option<String>,
+    /// The media type of the file.
+    pub content_type: Option<String>,
+    /// The width of the file, if it is an image.
+    pub width: Option<u32>,
+    /// The height of the file, if it is an image.
+    pub height: Option<u32>,
+}
+
+/// The payload of the [`MessageCreate`](crate::events::Event::MessageCreate),
+/// [`MessageUpdate`](crate::events::Event::MessageUpdate) and
+/// [`MessageDelete`](crate::events::Event::MessageDelete) events.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct MessageEvent {
+    /// The ID of the channel of the message.
+    pub channel_id: Snowflake,
+    /// The message.
+    pub message: Message,
+}
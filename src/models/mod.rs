@@ -0,0 +1,40 @@
+//! Typed models for the data sent to and received from the Discord IPC.
+mod achievement;
+mod activity;
+mod application;
+mod channel;
+mod guild;
+mod image;
+mod link;
+mod lobby;
+mod message;
+mod notification;
+mod oauth2;
+mod overlay;
+mod relationship;
+mod shortcut;
+mod snowflake;
+mod store;
+mod ticket;
+mod user;
+mod voice;
+
+pub use achievement::*;
+pub use activity::*;
+pub use application::*;
+pub use channel::*;
+pub use guild::*;
+pub use image::*;
+pub use link::*;
+pub use lobby::*;
+pub use message::*;
+pub use notification::*;
+pub use oauth2::*;
+pub use overlay::*;
+pub use relationship::*;
+pub use shortcut::*;
+pub use snowflake::*;
+pub use store::*;
+pub use ticket::*;
+pub use user::*;
+pub use voice::*;
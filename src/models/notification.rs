@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Message, Snowflake};
+
+/// A notification shown by Discord, such as for a mention or a direct message.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Notification {
+    /// The ID of the channel of the message that triggered the notification.
+    pub channel_id: Snowflake,
+    /// The message that triggered the notification.
+    pub message: Message,
+    /// The URL of the icon of the notification.
+    pub icon_url: Option<String>,
+    /// The title of the notification.
+    pub title: String,
+    /// The body of the notification.
+    pub body: String,
+}
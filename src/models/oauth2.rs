@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Application, Snowflake, User};
+use crate::unix_time;
+
+/// An OAuth2 scope that can be requested through
+/// [`DiscordIpc::authorize`](crate::DiscordIpc::authorize).
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, strum::Display, strum::EnumString, Debug)]
+#[allow(missing_docs)]
+pub enum Scope {
+    #[serde(rename = "activities.read")]
+    #[strum(serialize = "activities.read")]
+    ActivitiesRead,
+    #[serde(rename = "activities.write")]
+    #[strum(serialize = "activities.write")]
+    ActivitiesWrite,
+    #[serde(rename = "applications.builds.read")]
+    #[strum(serialize = "applications.builds.read")]
+    ApplicationsBuildsRead,
+    #[serde(rename = "applications.commands")]
+    #[strum(serialize = "applications.commands")]
+    ApplicationsCommands,
+    #[serde(rename = "applications.entitlements")]
+    #[strum(serialize = "applications.entitlements")]
+    ApplicationsEntitlements,
+    #[serde(rename = "applications.store.update")]
+    #[strum(serialize = "applications.store.update")]
+    ApplicationsStoreUpdate,
+    #[serde(rename = "bot")]
+    #[strum(serialize = "bot")]
+    Bot,
+    #[serde(rename = "connections")]
+    #[strum(serialize = "connections")]
+    Connections,
+    #[serde(rename = "email")]
+    #[strum(serialize = "email")]
+    Email,
+    #[serde(rename = "gdm.join")]
+    #[strum(serialize = "gdm.join")]
+    GdmJoin,
+    #[serde(rename = "guilds")]
+    #[strum(serialize = "guilds")]
+    Guilds,
+    #[serde(rename = "guilds.join")]
+    #[strum(serialize = "guilds.join")]
+    GuildsJoin,
+    #[serde(rename = "guilds.members.read")]
+    #[strum(serialize = "guilds.members.read")]
+    GuildsMembersRead,
+    #[serde(rename = "identify")]
+    #[strum(serialize = "identify")]
+    Identify,
+    #[serde(rename = "messages.read")]
+    #[strum(serialize = "messages.read")]
+    MessagesRead,
+    #[serde(rename = "relationships.read")]
+    #[strum(serialize = "relationships.read")]
+    RelationshipsRead,
+    #[serde(rename = "rpc")]
+    #[strum(serialize = "rpc")]
+    Rpc,
+    #[serde(rename = "rpc.activities.write")]
+    #[strum(serialize = "rpc.activities.write")]
+    RpcActivitiesWrite,
+    #[serde(rename = "rpc.notifications.read")]
+    #[strum(serialize = "rpc.notifications.read")]
+    RpcNotificationsRead,
+    #[serde(rename = "rpc.voice.read")]
+    #[strum(serialize = "rpc.voice.read")]
+    RpcVoiceRead,
+    #[serde(rename = "rpc.voice.write")]
+    #[strum(serialize = "rpc.voice.write")]
+    RpcVoiceWrite,
+    #[serde(rename = "webhook.incoming")]
+    #[strum(serialize = "webhook.incoming")]
+    WebhookIncoming,
+    /// A scope not known to this crate.
+    #[serde(other)]
+    #[strum(disabled)]
+    Unknown,
+}
+
+/// The arguments of an [`Authorize`](crate::ActivityCmd::Authorize) command.
+#[derive(Serialize, Debug)]
+pub(crate) struct AuthorizeArgs<'a> {
+    pub client_id: Snowflake,
+    pub scopes: &'a [Scope],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rpc_token: Option<&'a str>,
+}
+
+/// The response to an [`Authorize`](crate::ActivityCmd::Authorize) command.
+#[derive(Deserialize, Debug)]
+pub(crate) struct AuthorizeResponse {
+    pub code: String,
+}
+
+/// The arguments of an [`Authenticate`](crate::ActivityCmd::Authenticate) command.
+#[derive(Serialize, Debug)]
+pub(crate) struct AuthenticateArgs<'a> {
+    pub access_token: &'a str,
+}
+
+/// The response to an [`Authenticate`](crate::ActivityCmd::Authenticate) command.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct AuthenticateResponse {
+    /// The access token that was used to authenticate.
+    pub access_token: String,
+    /// The authenticated user.
+    pub user: User,
+    /// The scopes the session was authenticated with.
+    pub scopes: Vec<Scope>,
+    /// When the access token expires, as an ISO 8601 timestamp.
+    pub expires: String,
+    /// The application the session was authenticated for.
+    pub application: Application,
+}
+
+/// An OAuth2 access token, as returned by Discord's token endpoint.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Token {
+    /// The access token, passed to [`DiscordIpc::authenticate`](crate::DiscordIpc::authenticate).
+    pub access_token: String,
+    /// The type of the token, usually `"Bearer"`.
+    pub token_type: String,
+    /// The number of seconds until the access token expires.
+    pub expires_in: u64,
+    /// The token used to request a new access token once this one expires.
+    pub refresh_token: Option<String>,
+    /// The scopes granted to the token, separated by spaces.
+    pub scope: String,
+}
+
+/// An access token along with the time it expires, as saved by a
+/// [`TokenStore`](crate::oauth2::TokenStore).
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct StoredToken {
+    /// The access token.
+    #[serde(flatten)]
+    pub token: Token,
+    /// When the access token expires, in seconds since the Unix epoch.
+    pub expires_at: u64,
+}
+
+impl StoredToken {
+    /// Creates a new `StoredToken`, expiring `expires_in` seconds from now.
+    pub fn new(token: Token) -> Self {
+        let expires_at = unix_time().saturating_add(token.expires_in);
+        Self { token, expires_at }
+    }
+
+    /// Whether the access token has expired.
+    pub fn is_expired(&self) -> bool {
+        unix_time() >= self.expires_at
+    }
+}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+use super::ActivityActionType;
+
+/// The state of the Discord overlay in the application.
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct OverlayState {
+    /// Whether the user enabled the overlay.
+    pub enabled: bool,
+    /// Whether the overlay is locked, letting the application receive input.
+    pub locked: bool,
+}
+
+/// The arguments of a [`SetOverlayLocked`](crate::ActivityCmd::SetOverlayLocked) command.
+#[derive(Serialize, Debug)]
+pub(crate) struct SetOverlayLockedArgs {
+    pub locked: bool,
+    pub pid: u32,
+}
+
+/// The arguments of an [`OpenOverlayActivityInvite`](crate::ActivityCmd::OpenOverlayActivityInvite) command.
+#[derive(Serialize, Debug)]
+pub(crate) struct OpenOverlayActivityInviteArgs {
+    #[serde(rename = "type")]
+    pub kind: ActivityActionType,
+    pub pid: u32,
+}
+
+/// The arguments of an [`OpenOverlayGuildInvite`](crate::ActivityCmd::OpenOverlayGuildInvite) command.
+#[derive(Serialize, Debug)]
+pub(crate) struct OpenOverlayGuildInviteArgs<'a> {
+    pub code: &'a str,
+    pub pid: u32,
+}
+
+/// The arguments of commands and subscriptions targeting the overlay of the application.
+#[derive(Serialize, Debug)]
+pub(crate) struct OverlayArgs {
+    pub pid: u32,
+}
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use strum::FromRepr;
+
+use super::{Snowflake, User, UserActivity};
+
+/// The relationship between the user and another user.
+#[derive(FromRepr, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[repr(u8)]
+pub enum RelationshipType {
+    /// There is no relationship, such as after a friend was removed.
+    None = 0,
+    /// The users are friends.
+    Friend = 1,
+    /// The user blocked the other user.
+    Blocked = 2,
+    /// The other user sent a friend request to the user.
+    PendingIncoming = 3,
+    /// The user sent a friend request to the other user.
+    PendingOutgoing = 4,
+    /// The users are not friends, but have played together recently.
+    Implicit = 5,
+    /// A relationship type not known to this crate.
+    Unknown = u8::MAX,
+}
+impl Serialize for RelationshipType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+impl<'de> Deserialize<'de> for RelationshipType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let kind = u8::deserialize(deserializer)?;
+
+        Ok(RelationshipType::from_repr(kind).unwrap_or(RelationshipType::Unknown))
+    }
+}
+
+/// The online status of a user.
+#[allow(missing_docs)]
+#[derive(Serialize, Deserialize, Copy, Clone, Default, Eq, PartialEq, Hash, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Online,
+    Idle,
+    #[serde(rename = "dnd")]
+    DoNotDisturb,
+    Invisible,
+    #[default]
+    Offline,
+    /// A status not known to this crate.
+    #[serde(other)]
+    Unknown,
+}
+
+/// The presence of a user: their online status and current activity.
+#[derive(Serialize, Deserialize, Clone, Default, Eq, PartialEq, Hash, Debug)]
+pub struct Presence {
+    /// The online status of the user.
+    #[serde(default)]
+    pub status: Status,
+    /// The current activity of the user, if any.
+    pub activity: Option<UserActivity>,
+}
+
+/// A relationship between the user and another user.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Relationship {
+    /// The type of the relationship.
+    #[serde(rename = "type")]
+    pub kind: RelationshipType,
+    /// The other user.
+    pub user: User,
+    /// The presence of the other user.
+    #[serde(default)]
+    pub presence: Presence,
+}
+
+/// The arguments of a [`GetUser`](crate::ActivityCmd::GetUser) command.
+#[derive(Serialize, Debug)]
+pub(crate) struct GetUserArgs {
+    pub id: Snowflake,
+}
+
+/// The response to a [`GetRelationships`](crate::ActivityCmd::GetRelationships) command.
+#[derive(Deserialize, Debug)]
+pub(crate) struct RelationshipsResponse {
+    pub relationships: Vec<Relationship>,
+}
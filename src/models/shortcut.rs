@@ -0,0 +1,61 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use strum::FromRepr;
+
+/// The type of a key in a [`ShortcutKeyCombo`].
+#[allow(missing_docs)]
+#[derive(FromRepr, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[repr(u8)]
+pub enum KeyType {
+    KeyboardKey = 0,
+    MouseButton = 1,
+    KeyboardModifierKey = 2,
+    GamepadButton = 3,
+    /// A key type not known to this crate.
+    Unknown = u8::MAX,
+}
+impl Serialize for KeyType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+impl<'de> Deserialize<'de> for KeyType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let kind = u8::deserialize(deserializer)?;
+
+        Ok(KeyType::from_repr(kind).unwrap_or(KeyType::Unknown))
+    }
+}
+
+/// A single key of a keyboard shortcut, such as the push to talk shortcut.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ShortcutKeyCombo {
+    /// The type of the key.
+    #[serde(rename = "type")]
+    pub kind: KeyType,
+    /// The key code.
+    pub code: u32,
+    /// The name of the key.
+    pub name: String,
+}
+
+/// The payload of a [`CaptureShortcutChange`](crate::events::Event::CaptureShortcutChange) event.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct CaptureShortcutChange {
+    /// The keys of the shortcut captured so far.
+    pub shortcut: Vec<ShortcutKeyCombo>,
+}
+
+/// Whether a [`CaptureShortcut`](crate::ActivityCmd::CaptureShortcut) command
+/// starts or stops capturing.
+#[derive(Serialize, Copy, Clone, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub(crate) enum CaptureShortcutAction {
+    Start,
+    Stop,
+}
+
+/// The arguments of a [`CaptureShortcut`](crate::ActivityCmd::CaptureShortcut) command.
+#[derive(Serialize, Debug)]
+pub(crate) struct CaptureShortcutArgs {
+    pub action: CaptureShortcutAction,
+}
@@ -0,0 +1,134 @@
+use std::{
+    fmt,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Error;
+
+/// The Discord epoch (the first second of 2015), in milliseconds since the Unix epoch.
+pub const DISCORD_EPOCH: u64 = 1_420_070_400_000;
+
+/// A unique Discord ID, used for users, guilds, channels, applications and so on.
+///
+/// Discord sends snowflakes as strings, but they can be parsed from either
+/// strings or numbers. They are always serialized as strings.
+///
+/// # Examples
+/// ```
+/// use discord_ipc_rp::models::Snowflake;
+///
+/// let id: Snowflake = "175928847299117063".parse().unwrap();
+/// assert_eq!(id.timestamp(), 1462015105796);
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Snowflake(u64);
+
+impl Snowflake {
+    /// Creates a new `Snowflake` from its raw value.
+    pub const fn new(id: u64) -> Self {
+        Self(id)
+    }
+
+    /// Returns the raw value of the snowflake.
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+
+    /// The time the snowflake was created, in milliseconds since the Unix epoch.
+    pub const fn timestamp(self) -> u64 {
+        (self.0 >> 22) + DISCORD_EPOCH
+    }
+
+    /// The time the snowflake was created.
+    pub fn created_at(self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.timestamp())
+    }
+
+    /// The internal ID of the worker that generated the snowflake.
+    pub const fn worker_id(self) -> u8 {
+        ((self.0 & 0x3E0000) >> 17) as u8
+    }
+
+    /// The internal ID of the process that generated the snowflake.
+    pub const fn process_id(self) -> u8 {
+        ((self.0 & 0x1F000) >> 12) as u8
+    }
+
+    /// The sequence number of the snowflake, incremented for every ID
+    /// generated on the same process within the same millisecond.
+    pub const fn sequence(self) -> u16 {
+        (self.0 & 0xFFF) as u16
+    }
+}
+
+impl From<u64> for Snowflake {
+    fn from(id: u64) -> Self {
+        Self(id)
+    }
+}
+
+impl From<Snowflake> for u64 {
+    fn from(id: Snowflake) -> Self {
+        id.0
+    }
+}
+
+impl FromStr for Snowflake {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse::<u64>() {
+            Ok(id) if id != 0 && !s.starts_with('+') => Ok(Self(id)),
+            _ => Err(Error::InvalidSnowflake(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Snowflake {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Serialize for Snowflake {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Snowflake {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = Snowflake;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a snowflake as a string or integer")
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                match v {
+                    0 => Err(E::invalid_value(serde::de::Unexpected::Unsigned(v), &self)),
+                    _ => Ok(Snowflake(v)),
+                }
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                u64::try_from(v)
+                    .map_err(|_| E::invalid_value(serde::de::Unexpected::Signed(v), &self))
+                    .and_then(|v| self.visit_u64(v))
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse()
+                    .map_err(|_| E::invalid_value(serde::de::Unexpected::Str(v), &self))
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use strum::FromRepr;
+
+use super::Snowflake;
+
+/// The type of a [`Sku`].
+#[derive(FromRepr, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[repr(u8)]
+pub enum SkuType {
+    /// The application itself.
+    Application = 1,
+    /// Downloadable content of the application.
+    Dlc = 2,
+    /// An item that can be consumed, and purchased again.
+    Consumable = 3,
+    /// A bundle of other SKUs.
+    Bundle = 4,
+    /// A SKU type not known to this crate.
+    Unknown = u8::MAX,
+}
+impl Serialize for SkuType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+impl<'de> Deserialize<'de> for SkuType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let kind = u8::deserialize(deserializer)?;
+
+        Ok(SkuType::from_repr(kind).unwrap_or(SkuType::Unknown))
+    }
+}
+
+/// Something the user can purchase in the store of the application.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Sku {
+    /// The ID of the SKU.
+    pub id: Snowflake,
+    /// The type of the SKU.
+    #[serde(rename = "type")]
+    pub kind: SkuType,
+    /// The name of the SKU.
+    pub name: String,
+    /// The price of the SKU, if it is for sale.
+    pub price: Option<SkuPrice>,
+}
+
+/// The price of a [`Sku`].
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct SkuPrice {
+    /// The amount, in the smallest unit of the currency (such as cents).
+    pub amount: u32,
+    /// The ISO 4217 code of the currency.
+    pub currency: String,
+}
+
+/// How an [`Entitlement`] was granted.
+#[allow(missing_docs)]
+#[derive(FromRepr, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[repr(u8)]
+pub enum EntitlementType {
+    Purchase = 1,
+    PremiumSubscription = 2,
+    DeveloperGift = 3,
+    TestModePurchase = 4,
+    FreePurchase = 5,
+    UserGift = 6,
+    PremiumPurchase = 7,
+    ApplicationSubscription = 8,
+    /// An entitlement type not known to this crate.
+    Unknown = u8::MAX,
+}
+impl Serialize for EntitlementType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+impl<'de> Deserialize<'de> for EntitlementType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let kind = u8::deserialize(deserializer)?;
+
+        Ok(EntitlementType::from_repr(kind).unwrap_or(EntitlementType::Unknown))
+    }
+}
+
+/// The ownership of a [`Sku`] by the user.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Entitlement {
+    /// The ID of the entitlement.
+    pub id: Snowflake,
+    /// How the entitlement was granted.
+    #[serde(rename = "type")]
+    pub kind: EntitlementType,
+    /// The ID of the owned SKU.
+    pub sku_id: Snowflake,
+    /// The ID of the application of the SKU.
+    pub application_id: Option<Snowflake>,
+    /// The ID of the user owning the SKU.
+    pub user_id: Option<Snowflake>,
+}
+
+/// The arguments of a [`StartPurchase`](crate::ActivityCmd::StartPurchase) command.
+#[derive(Serialize, Debug)]
+pub(crate) struct StartPurchaseArgs {
+    pub sku_id: Snowflake,
+    pub pid: u32,
+}
@@ -0,0 +1,89 @@
+use std::str::FromStr;
+
+use base64::{
+    alphabet,
+    engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    Engine,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::{Entitlement, Snowflake, User};
+use crate::Error;
+
+/// Decodes the payload of tickets regardless of padding.
+const CONFIG: GeneralPurposeConfig = GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent);
+
+/// A ticket signed by Discord, proving who the user is and what they own,
+/// to verify on a server.
+///
+/// A ticket has the format `<version>.<signature>.<payload>`, where the
+/// payload is the base64 encoded JSON of its [`claims`](SignedTicket::claims).
+/// The claims are decoded, but not verified: the signature must be checked
+/// against the public key of the application before trusting them.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SignedTicket {
+    /// The raw ticket, as returned by Discord.
+    pub ticket: String,
+    /// The version of the format of the ticket.
+    pub version: String,
+    /// The signature of the ticket.
+    pub signature: String,
+    /// The decoded payload of the ticket.
+    pub claims: TicketClaims,
+}
+
+impl FromStr for SignedTicket {
+    type Err = Error;
+
+    fn from_str(ticket: &str) -> Result<Self, Self::Err> {
+        let mut parts = ticket.splitn(3, '.');
+        let (Some(version), Some(signature), Some(payload)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(Error::InvalidTicket);
+        };
+        let payload = GeneralPurpose::new(&alphabet::STANDARD, CONFIG)
+            .decode(payload)
+            .or_else(|_| GeneralPurpose::new(&alphabet::URL_SAFE, CONFIG).decode(payload))
+            .map_err(|_| Error::InvalidTicket)?;
+
+        Ok(Self {
+            ticket: ticket.to_string(),
+            version: version.to_string(),
+            signature: signature.to_string(),
+            claims: serde_json::from_slice(&payload).map_err(|_| Error::InvalidTicket)?,
+        })
+    }
+}
+
+/// The claims of a [`SignedTicket`].
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct TicketClaims {
+    /// The ID of the application the ticket was issued for.
+    pub application_id: Option<Snowflake>,
+    /// The ID of the user the ticket was issued to.
+    pub user_id: Option<Snowflake>,
+    /// The user the ticket was issued to.
+    pub user: Option<User>,
+    /// The entitlements of the user to the SKUs of the application.
+    #[serde(default)]
+    pub entitlements: Vec<Entitlement>,
+    /// The other claims of the ticket.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// The response of commands returning a ticket, which may be wrapped in an object.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub(crate) enum TicketResponse {
+    Ticket(String),
+    Object { ticket: String },
+}
+
+impl TicketResponse {
+    pub fn parse(self) -> crate::Result<SignedTicket> {
+        let (TicketResponse::Ticket(ticket) | TicketResponse::Object { ticket }) = self;
+
+        ticket.parse()
+    }
+}
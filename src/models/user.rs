@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+use super::Snowflake;
+
+/// A Discord user.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct User {
+    /// The ID of the user.
+    pub id: Snowflake,
+    /// The username of the user.
+    pub username: String,
+    /// The legacy discriminator of the user, `"0"` for migrated users.
+    pub discriminator: Option<String>,
+    /// The display name of the user, if set.
+    pub global_name: Option<String>,
+    /// The avatar hash of the user, if set.
+    pub avatar: Option<String>,
+    /// Whether the user is a bot.
+    #[serde(default)]
+    pub bot: bool,
+    /// The public flags of the user.
+    pub flags: Option<u64>,
+    /// The type of Nitro subscription of the user.
+    pub premium_type: Option<u8>,
+}
@@ -0,0 +1,315 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::{ShortcutKeyCombo, Snowflake, User};
+
+/// The voice settings of the user.
+///
+/// To change settings, modify the settings returned by
+/// [`DiscordIpc::get_voice_settings`](crate::DiscordIpc::get_voice_settings)
+/// and pass them to [`DiscordIpc::set_voice_settings`](crate::DiscordIpc::set_voice_settings).
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct VoiceSettings {
+    /// The input device settings.
+    pub input: VoiceDeviceSettings,
+    /// The output device settings.
+    pub output: VoiceDeviceSettings,
+    /// The voice mode settings.
+    pub mode: VoiceModeSettings,
+    /// Whether automatic gain control is enabled.
+    pub automatic_gain_control: bool,
+    /// Whether echo cancellation is enabled.
+    pub echo_cancellation: bool,
+    /// Whether background noise is suppressed.
+    pub noise_suppression: bool,
+    /// Whether voice quality of service is enabled.
+    pub qos: bool,
+    /// Whether a warning is shown when the microphone is silent.
+    pub silence_warning: bool,
+    /// Whether the user is deafened.
+    pub deaf: bool,
+    /// Whether the user is muted.
+    pub mute: bool,
+}
+
+impl VoiceSettings {
+    /// The fields of these settings that differ from `current`, excluding
+    /// the fields that cannot be changed.
+    pub(crate) fn changes_from(&self, current: &VoiceSettings) -> serde_json::Result<Value> {
+        let mut changes = diff(&serde_json::to_value(current)?, &serde_json::to_value(self)?);
+
+        if let Value::Object(changes) = &mut changes {
+            for device in ["input", "output"] {
+                if let Some(Value::Object(settings)) = changes.get_mut(device) {
+                    settings.remove("available_devices");
+                    if settings.is_empty() {
+                        changes.remove(device);
+                    }
+                }
+            }
+        }
+
+        Ok(changes)
+    }
+}
+
+/// The settings of an input or output device.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct VoiceDeviceSettings {
+    /// The ID of the device.
+    pub device_id: String,
+    /// The volume of the device, between 0 and 100 for input,
+    /// and 0 and 200 for output.
+    pub volume: f32,
+    /// The devices that are available. This cannot be changed.
+    #[serde(default)]
+    pub available_devices: Vec<AudioDevice>,
+}
+
+/// An audio device.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct AudioDevice {
+    /// The ID of the device.
+    pub id: String,
+    /// The name of the device.
+    pub name: String,
+}
+
+/// The voice mode settings.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct VoiceModeSettings {
+    /// The voice mode.
+    #[serde(rename = "type")]
+    pub kind: VoiceMode,
+    /// Whether the voice activity threshold is set automatically.
+    pub auto_threshold: bool,
+    /// The voice activity threshold, in dB between -100 and 0.
+    pub threshold: f32,
+    /// The push to talk shortcut.
+    pub shortcut: Vec<ShortcutKeyCombo>,
+    /// The push to talk release delay, in milliseconds between 0 and 2000.
+    pub delay: f32,
+}
+
+/// How the user's voice is transmitted.
+#[allow(missing_docs)]
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum VoiceMode {
+    VoiceActivity,
+    PushToTalk,
+}
+
+/// The voice state of a user in a voice channel.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct VoiceState {
+    /// The server-side state of the user.
+    pub voice_state: VoiceStateFlags,
+    /// The user.
+    pub user: User,
+    /// The nickname of the user in the guild, if set.
+    pub nick: Option<String>,
+    /// The local volume of the user, between 0 and 200.
+    pub volume: f32,
+    /// Whether the user is locally muted.
+    pub mute: bool,
+    /// The local left and right pan of the user.
+    pub pan: Pan,
+}
+
+impl VoiceState {
+    /// The local voice settings of this user, as set with
+    /// [`DiscordIpc::set_user_voice_settings`](crate::DiscordIpc::set_user_voice_settings).
+    pub fn user_voice_settings(&self) -> UserVoiceSettings {
+        UserVoiceSettings {
+            user_id: self.user.id,
+            pan: Some(self.pan),
+            volume: Some(self.volume),
+            mute: Some(self.mute),
+        }
+    }
+}
+
+/// The server-side voice state of a user.
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct VoiceStateFlags {
+    /// Whether the user is muted by the guild.
+    pub mute: bool,
+    /// Whether the user is deafened by the guild.
+    pub deaf: bool,
+    /// Whether the user has muted themselves.
+    pub self_mute: bool,
+    /// Whether the user has deafened themselves.
+    pub self_deaf: bool,
+    /// Whether the user is suppressed, such as an audience member of a stage.
+    pub suppress: bool,
+}
+
+/// The left and right pan of a user, each between 0.0 and 1.0.
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+pub struct Pan {
+    /// The left pan.
+    pub left: f32,
+    /// The right pan.
+    pub right: f32,
+}
+
+impl Default for Pan {
+    fn default() -> Self {
+        Self { left: 1.0, right: 1.0 }
+    }
+}
+
+/// The local voice settings of another user.
+///
+/// Only the fields that are set are changed by
+/// [`DiscordIpc::set_user_voice_settings`](crate::DiscordIpc::set_user_voice_settings).
+///
+/// Note that all methods return `Self`, and can be chained
+/// for fluency
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct UserVoiceSettings {
+    /// The ID of the user.
+    pub user_id: Snowflake,
+    /// The left and right pan of the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pan: Option<Pan>,
+    /// The volume of the user, between 0 and 200.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume: Option<f32>,
+    /// Whether the user is muted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mute: Option<bool>,
+}
+
+impl UserVoiceSettings {
+    /// Creates a new `UserVoiceSettings` for the given user, changing nothing
+    pub fn new(user_id: Snowflake) -> Self {
+        Self {
+            user_id,
+            pan: None,
+            volume: None,
+            mute: None,
+        }
+    }
+
+    /// Sets the left and right pan of the user
+    pub fn pan(mut self, pan: Pan) -> Self {
+        self.pan = Some(pan);
+        self
+    }
+
+    /// Sets the volume of the user, between 0 and 200
+    pub fn volume(mut self, volume: f32) -> Self {
+        self.volume = Some(volume);
+        self
+    }
+
+    /// Sets whether the user is muted
+    pub fn mute(mut self, mute: bool) -> Self {
+        self.mute = Some(mute);
+        self
+    }
+}
+
+/// The data of a [`VoiceChannelSelect`](crate::ActivityEvent::VoiceChannelSelect) event.
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct VoiceChannelSelect {
+    /// The ID of the voice channel the user joined, or `None` if they left.
+    pub channel_id: Option<Snowflake>,
+    /// The ID of the guild the channel is in, if any.
+    pub guild_id: Option<Snowflake>,
+}
+
+/// The data of the [`SpeakingStart`](crate::ActivityEvent::SpeakingStart) and
+/// [`SpeakingStop`](crate::ActivityEvent::SpeakingStop) events.
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Speaking {
+    /// The ID of the user that started or stopped speaking.
+    pub user_id: Snowflake,
+    /// The ID of the channel the user is speaking in.
+    pub channel_id: Option<Snowflake>,
+}
+
+/// The state of the voice connection of the user.
+#[allow(missing_docs)]
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, strum::Display, Debug)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum VoiceConnectionState {
+    Disconnected,
+    AwaitingEndpoint,
+    Authenticating,
+    Connecting,
+    Connected,
+    VoiceDisconnected,
+    VoiceConnecting,
+    VoiceConnected,
+    NoRoute,
+    IceChecking,
+    /// A connection state not known to this crate.
+    #[serde(other)]
+    Unknown,
+}
+
+/// The data of a [`VoiceConnectionStatus`](crate::ActivityEvent::VoiceConnectionStatus) event.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct VoiceConnectionStatus {
+    /// The state of the voice connection.
+    pub state: VoiceConnectionState,
+    /// The hostname of the voice server the user is connected to.
+    pub hostname: Option<String>,
+    /// The latest pings to the voice server, in milliseconds.
+    #[serde(default)]
+    pub pings: Vec<Ping>,
+    /// The average ping to the voice server, in milliseconds.
+    pub average_ping: Option<f64>,
+    /// The last ping to the voice server, in milliseconds.
+    pub last_ping: Option<f64>,
+}
+
+/// A ping to the voice server.
+///
+/// Older clients only send the value of the ping, without the time.
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+#[serde(from = "PingRepr")]
+pub struct Ping {
+    /// When the ping was measured, in milliseconds since the Unix epoch.
+    pub time: Option<u64>,
+    /// The ping, in milliseconds.
+    pub value: f64,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PingRepr {
+    Value(f64),
+    Timed { time: u64, value: f64 },
+}
+
+impl From<PingRepr> for Ping {
+    fn from(ping: PingRepr) -> Self {
+        match ping {
+            PingRepr::Value(value) => Ping { time: None, value },
+            PingRepr::Timed { time, value } => Ping { time: Some(time), value },
+        }
+    }
+}
+
+/// Returns the fields of `new` that differ from `old`, recursing into objects.
+pub(crate) fn diff(old: &Value, new: &Value) -> Value {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            let changed: Map<String, Value> = new
+                .iter()
+                .filter(|(key, value)| old.get(*key) != Some(value))
+                .map(|(key, value)| match old.get(key) {
+                    Some(old) => (key.clone(), diff(old, value)),
+                    None => (key.clone(), value.clone()),
+                })
+                .collect();
+
+            Value::Object(changed)
+        }
+        _ => new.clone(),
+    }
+}
@@ -0,0 +1,235 @@
+//! Provides a bridge forwarding the notifications shown by Discord to
+//! another notification system, such as the UI of a fullscreen game.
+use crate::{events::Event, models::Notification, subscription::Subscription, ActivityEvent, DiscordIpc, Result};
+
+/// A destination of the notifications shown by Discord.
+///
+/// Implemented for closures taking a [`Notification`], and on unix by
+/// [`FreedesktopSink`] when the `freedesktop` feature is enabled, which it
+/// is by default.
+pub trait NotificationSink {
+    /// Shows a notification.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the notification could not be shown.
+    fn notify(&mut self, notification: &Notification) -> Result<()>;
+}
+
+impl<F: FnMut(&Notification) -> Result<()>> NotificationSink for F {
+    fn notify(&mut self, notification: &Notification) -> Result<()> {
+        self(notification)
+    }
+}
+
+/// Forwards [`NotificationCreate`](ActivityEvent::NotificationCreate) events
+/// to a [`NotificationSink`].
+///
+/// Requires the [`Rpc`](crate::models::Scope::Rpc) and
+/// [`RpcNotificationsRead`](crate::models::Scope::RpcNotificationsRead) scopes.
+///
+/// # Examples
+/// ```no_run
+/// # use discord_ipc_rp::{models::Notification, notifications::NotificationBridge, DiscordIpc, DiscordIpcClient};
+/// # fn main() -> discord_ipc_rp::Result<()> {
+/// # let mut client = DiscordIpcClient::new("771124766517755954")?;
+/// let mut bridge = NotificationBridge::subscribe(&mut client, |notification: &Notification| {
+///     println!("{}: {}", notification.title, notification.body);
+///     Ok(())
+/// })?;
+/// loop {
+///     let event = client.recv_event()?;
+///     bridge.handle_event(&event)?;
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct NotificationBridge<S: NotificationSink> {
+    sink: S,
+    subscription: Subscription,
+}
+
+impl<S: NotificationSink> NotificationBridge<S> {
+    /// Creates a new `NotificationBridge` forwarding to `sink`, without
+    /// subscribing to any events.
+    pub fn new(sink: S) -> Self {
+        Self { sink, subscription: Subscription::new(ActivityEvent::NotificationCreate) }
+    }
+
+    /// Creates a new `NotificationBridge` forwarding to `sink`, subscribing
+    /// to the notifications of the user.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if subscribing failed.
+    pub fn subscribe<C: DiscordIpc>(client: &mut C, sink: S) -> Result<Self> {
+        let subscription = Subscription::subscribe(client, ActivityEvent::NotificationCreate)?;
+
+        Ok(Self { sink, subscription })
+    }
+
+    /// Unsubscribes from the notifications of the user, if the bridge was
+    /// created with [`subscribe`](Self::subscribe), and returns the sink.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if unsubscribing failed.
+    pub fn unsubscribe<C: DiscordIpc>(self, client: &mut C) -> Result<S> {
+        self.subscription.unsubscribe(client)?;
+
+        Ok(self.sink)
+    }
+
+    /// Forwards an event to the sink if it is a notification, and returns
+    /// whether it was forwarded.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the sink failed to show the notification.
+    pub fn handle_event(&mut self, event: &Event) -> Result<bool> {
+        let Event::NotificationCreate(notification) = event else {
+            return Ok(false);
+        };
+        self.sink.notify(notification)?;
+
+        Ok(true)
+    }
+
+    /// The sink of the bridge.
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// The sink of the bridge, mutably.
+    pub fn sink_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+}
+
+/// A [`NotificationSink`] showing notifications with the desktop notification
+/// server, as specified by freedesktop.org, over the D-Bus session bus.
+///
+/// The body of the notifications is escaped, as notification servers may
+/// interpret it as markup.
+///
+/// Note that all methods return `Self`, and can be chained
+/// for fluency
+///
+/// # Examples
+/// ```no_run
+/// use discord_ipc_rp::notifications::FreedesktopSink;
+///
+/// let sink = FreedesktopSink::new()?.app_name("My Game").timeout(Some(std::time::Duration::from_secs(5)));
+/// # Ok::<(), discord_ipc_rp::Error>(())
+/// ```
+#[cfg(all(unix, feature = "freedesktop"))]
+#[derive(Clone, Debug)]
+pub struct FreedesktopSink {
+    connection: zbus::blocking::Connection,
+    app_name: String,
+    icon: String,
+    timeout: i32,
+}
+
+#[cfg(all(unix, feature = "freedesktop"))]
+impl FreedesktopSink {
+    /// Creates a new `FreedesktopSink`, connecting to the session bus.
+    ///
+    /// # Errors
+    /// Returns [`Error::Notification`](crate::Error::Notification) if the
+    /// session bus could not be connected to.
+    pub fn new() -> Result<Self> {
+        let connection = zbus::blocking::Connection::session().map_err(|e| crate::Error::Notification(e.into()))?;
+
+        Ok(Self {
+            connection,
+            app_name: "Discord".to_string(),
+            icon: "discord".to_string(),
+            timeout: -1,
+        })
+    }
+
+    /// Sets the name of the application showing the notifications,
+    /// `Discord` by default
+    pub fn app_name(mut self, app_name: &str) -> Self {
+        self.app_name = app_name.to_string();
+        self
+    }
+
+    /// Sets the icon of the notifications, as an icon name or a `file://`
+    /// URI, `discord` by default. The [`icon_url`](Notification::icon_url)
+    /// of a notification is only used instead if it is a `file://` URI, as
+    /// notification servers do not load remote icons
+    pub fn icon(mut self, icon: &str) -> Self {
+        self.icon = icon.to_string();
+        self
+    }
+
+    /// Sets how long the notifications are shown, or `None` to let the
+    /// notification server decide, which is the default
+    pub fn timeout(mut self, timeout: Option<std::time::Duration>) -> Self {
+        self.timeout = timeout.map_or(-1, |timeout| timeout.as_millis().try_into().unwrap_or(i32::MAX));
+        self
+    }
+}
+
+#[cfg(all(unix, feature = "freedesktop"))]
+impl NotificationSink for FreedesktopSink {
+    fn notify(&mut self, notification: &Notification) -> Result<()> {
+        use std::collections::HashMap;
+        use zbus::zvariant::Value;
+
+        let icon = notification_icon(notification.icon_url.as_deref(), &self.icon);
+        let body = escape_markup(&notification.body);
+        let actions: &[&str] = &[];
+        let hints: HashMap<&str, Value> = HashMap::from([("category", Value::from("im.received"))]);
+        self.connection
+            .call_method(
+                Some("org.freedesktop.Notifications"),
+                "/org/freedesktop/Notifications",
+                Some("org.freedesktop.Notifications"),
+                "Notify",
+                &(&self.app_name, 0u32, icon, &notification.title, &body, actions, hints, self.timeout),
+            )
+            .map_err(|e| crate::Error::Notification(e.into()))?;
+
+        Ok(())
+    }
+}
+
+/// The icon of a notification: its icon URL if the notification server
+/// can load it, the default icon otherwise.
+#[cfg(all(unix, feature = "freedesktop"))]
+fn notification_icon<'a>(icon_url: Option<&'a str>, default: &'a str) -> &'a str {
+    icon_url.filter(|url| url.starts_with("file://")).unwrap_or(default)
+}
+
+/// Escapes the characters the notification markup gives a meaning to.
+#[cfg(all(unix, feature = "freedesktop"))]
+fn escape_markup(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(all(test, unix, feature = "freedesktop"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_icon() {
+        assert_eq!(notification_icon(None, "discord"), "discord");
+        assert_eq!(notification_icon(Some("https://cdn.discordapp.com/avatars/1/a.png"), "discord"), "discord");
+        assert_eq!(notification_icon(Some("file:///tmp/a.png"), "discord"), "file:///tmp/a.png");
+    }
+
+    #[test]
+    fn test_escape_markup() {
+        assert_eq!(escape_markup("<b>fish & chips</b>"), "&lt;b&gt;fish &amp; chips&lt;/b&gt;");
+        assert_eq!(escape_markup("plain text"), "plain text");
+    }
+}
@@ -0,0 +1,229 @@
+//! Provides the OAuth2 token exchange and storage used to authenticate
+//! RPC sessions via [`DiscordIpc::login`](crate::DiscordIpc::login).
+use std::{
+    fs,
+    io::{ErrorKind, Write},
+    path::PathBuf,
+};
+
+use crate::{
+    models::{Snowflake, StoredToken, Token},
+    Result,
+};
+
+/// Exchanges OAuth2 authorization codes and refresh tokens for access tokens.
+///
+/// Exchanging a code requires the client secret of the application, so this
+/// is usually done by a backend. Implement this trait to call your own
+/// backend, or use [`HttpTokenExchanger`] to call a token endpoint directly.
+pub trait TokenExchanger: Send {
+    /// Exchanges an authorization code returned by
+    /// [`DiscordIpc::authorize`](crate::DiscordIpc::authorize) for an access token.
+    fn exchange_code(&self, code: &str) -> Result<Token>;
+
+    /// Exchanges a refresh token for a new access token.
+    fn refresh_token(&self, refresh_token: &str) -> Result<Token>;
+}
+
+/// Persists access tokens between sessions, so that users do not have to
+/// authorize the application every time it starts.
+pub trait TokenStore: Send {
+    /// Loads the token saved for the given application, if any.
+    fn load(&self, client_id: Snowflake) -> Result<Option<StoredToken>>;
+
+    /// Saves the token for the given application, replacing any saved token.
+    fn save(&self, client_id: Snowflake, token: &StoredToken) -> Result<()>;
+
+    /// Removes the token saved for the given application.
+    fn clear(&self, client_id: Snowflake) -> Result<()>;
+}
+
+/// A [`TokenStore`] that saves each token to a JSON file named after the
+/// client ID of the application.
+///
+/// The files are only readable by the current user.
+#[derive(Clone, Debug)]
+pub struct FileTokenStore {
+    dir: PathBuf,
+}
+
+impl FileTokenStore {
+    /// Creates a new `FileTokenStore` in the user's data directory, which is
+    /// `$XDG_DATA_HOME/discord-ipc-rp` (`~/.local/share/discord-ipc-rp`) on
+    /// Linux, `~/Library/Application Support/discord-ipc-rp` on macOS and
+    /// `%APPDATA%\discord-ipc-rp` on Windows.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the data directory could not be determined.
+    pub fn new() -> Result<Self> {
+        let dir = Self::data_dir().ok_or_else(|| std::io::Error::new(ErrorKind::NotFound, "could not determine the data directory"))?;
+
+        Ok(Self::with_dir(dir.join("discord-ipc-rp")))
+    }
+
+    /// Creates a new `FileTokenStore` in the given directory.
+    pub fn with_dir<P: Into<PathBuf>>(dir: P) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The path of the file the token of the given application is saved to.
+    pub fn path(&self, client_id: Snowflake) -> PathBuf {
+        self.dir.join(format!("{}.json", client_id))
+    }
+
+    fn data_dir() -> Option<PathBuf> {
+        let var = |key| std::env::var_os(key).filter(|val| !val.is_empty()).map(PathBuf::from);
+
+        if cfg!(windows) {
+            var("APPDATA")
+        } else if cfg!(target_os = "macos") {
+            var("HOME").map(|home| home.join("Library/Application Support"))
+        } else {
+            var("XDG_DATA_HOME").or_else(|| var("HOME").map(|home| home.join(".local/share")))
+        }
+    }
+}
+
+impl TokenStore for FileTokenStore {
+    fn load(&self, client_id: Snowflake) -> Result<Option<StoredToken>> {
+        match fs::read(self.path(client_id)) {
+            Ok(data) => Ok(Some(serde_json::from_slice(&data)?)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save(&self, client_id: Snowflake, token: &StoredToken) -> Result<()> {
+        let mut dir = fs::DirBuilder::new();
+        dir.recursive(true);
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut dir, 0o700);
+        dir.create(&self.dir)?;
+
+        // write to a temporary file first, so that the token file is replaced
+        // atomically and never left half written
+        let path = self.path(client_id);
+        let temp = self.dir.join(format!("{}.{}.tmp", client_id, std::process::id()));
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let written = options.open(&temp).and_then(|mut file| {
+            // the mode is only applied when the file is created
+            #[cfg(unix)]
+            file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+            file.write_all(&serde_json::to_vec(token)?)?;
+            file.sync_all()?;
+            fs::rename(&temp, &path)
+        });
+        if let Err(e) = written {
+            let _ = fs::remove_file(&temp);
+            return Err(e.into());
+        }
+
+        log::debug!("Saved access token to {}", path.display());
+
+        Ok(())
+    }
+
+    fn clear(&self, client_id: Snowflake) -> Result<()> {
+        match fs::remove_file(self.path(client_id)) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A [`TokenExchanger`] that calls an OAuth2 token endpoint over HTTP.
+///
+/// By default, this calls Discord's token endpoint, but it can be pointed
+/// at any server implementing the same `/oauth2/token` endpoint.
+///
+/// Note that all methods return `Self`, and can be chained
+/// for fluency
+///
+/// # Examples
+/// ```
+/// use discord_ipc_rp::{models::Snowflake, oauth2::HttpTokenExchanger};
+///
+/// let exchanger = HttpTokenExchanger::new(Snowflake::new(771124766517755954))
+///     .client_secret("<some client secret>")
+///     .redirect_uri("http://localhost");
+/// ```
+#[cfg(feature = "http")]
+#[derive(Clone, Debug)]
+pub struct HttpTokenExchanger {
+    base_url: String,
+    client_id: Snowflake,
+    client_secret: Option<String>,
+    redirect_uri: Option<String>,
+    agent: ureq::Agent,
+}
+
+#[cfg(feature = "http")]
+impl HttpTokenExchanger {
+    /// The base URL of Discord's API.
+    pub const DEFAULT_BASE_URL: &'static str = "https://discord.com/api/v10";
+
+    /// Creates a new `HttpTokenExchanger` for the given application,
+    /// calling Discord's token endpoint.
+    pub fn new(client_id: Snowflake) -> Self {
+        Self {
+            base_url: Self::DEFAULT_BASE_URL.to_string(),
+            client_id,
+            client_secret: None,
+            redirect_uri: None,
+            agent: ureq::Agent::new(),
+        }
+    }
+
+    /// Sets the base URL of the server, the token endpoint
+    /// is `<base_url>/oauth2/token`
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sets the client secret of the application
+    pub fn client_secret(mut self, client_secret: &str) -> Self {
+        self.client_secret = Some(client_secret.to_string());
+        self
+    }
+
+    /// Sets the redirect URI sent with code exchanges
+    pub fn redirect_uri(mut self, redirect_uri: &str) -> Self {
+        self.redirect_uri = Some(redirect_uri.to_string());
+        self
+    }
+
+    fn request(&self, grant: [(&str, &str); 2]) -> Result<Token> {
+        let client_id = self.client_id.to_string();
+        let mut form = vec![("client_id", client_id.as_str())];
+        form.extend(grant);
+        if let Some(client_secret) = &self.client_secret {
+            form.push(("client_secret", client_secret));
+        }
+        if let Some(redirect_uri) = &self.redirect_uri {
+            form.push(("redirect_uri", redirect_uri));
+        }
+
+        self.agent
+            .post(&format!("{}/oauth2/token", self.base_url))
+            .send_form(&form)
+            .map_err(|e| crate::Error::TokenExchange(Box::new(e)))?
+            .into_json()
+            .map_err(|e| crate::Error::TokenExchange(Box::new(e)))
+    }
+}
+
+#[cfg(feature = "http")]
+impl TokenExchanger for HttpTokenExchanger {
+    fn exchange_code(&self, code: &str) -> Result<Token> {
+        self.request([("grant_type", "authorization_code"), ("code", code)])
+    }
+
+    fn refresh_token(&self, refresh_token: &str) -> Result<Token> {
+        self.request([("grant_type", "refresh_token"), ("refresh_token", refresh_token)])
+    }
+}
@@ -0,0 +1,127 @@
+//! Provides a cache of the user's relationships, kept up to date from
+//! the relationship events dispatched by Discord.
+use crate::{
+    events::Event,
+    models::{Presence, Relationship, RelationshipType, Snowflake},
+    ActivityEvent, DiscordIpc, Result,
+};
+
+/// The relationships of the user: their friends, blocked users and pending
+/// friend requests, with the presence and activity of each user.
+///
+/// The cache is kept up to date by passing every received event to
+/// [`handle_event`](Relationships::handle_event).
+///
+/// Requires the [`RelationshipsRead`](crate::models::Scope::RelationshipsRead) scope.
+///
+/// # Examples
+/// ```no_run
+/// # use discord_ipc_rp::{models::Snowflake, relationships::Relationships, DiscordIpc, DiscordIpcClient};
+/// # fn main() -> discord_ipc_rp::Result<()> {
+/// # let application_id = Snowflake::new(771124766517755954);
+/// # let mut client = DiscordIpcClient::new("771124766517755954")?;
+/// let mut relationships = Relationships::subscribe(&mut client)?;
+/// loop {
+///     let event = client.recv_event()?;
+///     if relationships.handle_event(&event) {
+///         for friend in relationships.playing(application_id) {
+///             println!("{} is playing", friend.user.username);
+///         }
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct Relationships {
+    relationships: Vec<Relationship>,
+}
+
+impl Relationships {
+    /// Creates a new `Relationships`, subscribing to the relationship events
+    /// and loading the relationships of the user.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if subscribing or loading the relationships failed.
+    pub fn subscribe<C: DiscordIpc>(client: &mut C) -> Result<Self> {
+        client.subscribe(ActivityEvent::RelationshipUpdate, crate::Empty)?;
+
+        Ok(Self {
+            relationships: client.get_relationships()?,
+        })
+    }
+
+    /// Unsubscribes from the relationship events.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if unsubscribing failed.
+    pub fn unsubscribe<C: DiscordIpc>(self, client: &mut C) -> Result<()> {
+        client.unsubscribe(ActivityEvent::RelationshipUpdate, crate::Empty)
+    }
+
+    /// Updates the cache from an event, and returns whether it changed.
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        let Event::RelationshipUpdate(relationship) = event else {
+            return false;
+        };
+
+        let index = self.relationships.iter().position(|r| r.user.id == relationship.user.id);
+        match (index, relationship.kind) {
+            (Some(index), RelationshipType::None) => {
+                self.relationships.remove(index);
+            }
+            (Some(index), _) if self.relationships[index] != **relationship => {
+                self.relationships[index] = (**relationship).clone();
+            }
+            (None, kind) if kind != RelationshipType::None => self.relationships.push((**relationship).clone()),
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// All of the relationships of the user.
+    pub fn all(&self) -> impl Iterator<Item = &Relationship> {
+        self.relationships.iter()
+    }
+
+    /// The relationship with a user, if any.
+    pub fn get(&self, user_id: Snowflake) -> Option<&Relationship> {
+        self.relationships.iter().find(|relationship| relationship.user.id == user_id)
+    }
+
+    /// The presence of a user the user has a relationship with.
+    pub fn presence(&self, user_id: Snowflake) -> Option<&Presence> {
+        self.get(user_id).map(|relationship| &relationship.presence)
+    }
+
+    /// The friends of the user.
+    pub fn friends(&self) -> impl Iterator<Item = &Relationship> {
+        self.of_kind(RelationshipType::Friend)
+    }
+
+    /// The users blocked by the user.
+    pub fn blocked(&self) -> impl Iterator<Item = &Relationship> {
+        self.of_kind(RelationshipType::Blocked)
+    }
+
+    /// The users that sent a friend request to the user.
+    pub fn incoming_requests(&self) -> impl Iterator<Item = &Relationship> {
+        self.of_kind(RelationshipType::PendingIncoming)
+    }
+
+    /// The users the user sent a friend request to.
+    pub fn outgoing_requests(&self) -> impl Iterator<Item = &Relationship> {
+        self.of_kind(RelationshipType::PendingOutgoing)
+    }
+
+    /// The friends of the user whose current activity is of an application.
+    pub fn playing(&self, application_id: Snowflake) -> impl Iterator<Item = &Relationship> {
+        self.friends().filter(move |friend| {
+            friend.presence.activity.as_ref().is_some_and(|activity| activity.application_id == Some(application_id))
+        })
+    }
+
+    fn of_kind(&self, kind: RelationshipType) -> impl Iterator<Item = &Relationship> {
+        self.relationships.iter().filter(move |relationship| relationship.kind == kind)
+    }
+}
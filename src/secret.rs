@@ -0,0 +1,164 @@
+//! Provides signed activity secrets, so that the join, spectate and match
+//! secrets of an [`Activity`](crate::activity::Activity) cannot be forged.
+//!
+//! A secret contains a [`SecretPayload`] and an HMAC-SHA256 tag, computed with
+//! a key only known to the game. When an [`ActivityJoin`](crate::events::Event::ActivityJoin)
+//! or [`ActivitySpectate`](crate::events::Event::ActivitySpectate) event is
+//! received, the secret is verified and decoded with the same key.
+//!
+//! # Examples
+//! ```
+//! use std::time::Duration;
+//! use discord_ipc_rp::secret::{SecretKind, SecretPayload, SecretSigner};
+//!
+//! let signer = SecretSigner::new(b"<some application key>");
+//! let payload = SecretPayload::new("party-1", Duration::from_secs(3600)).server("203.0.113.7:27015");
+//! let join = signer.sign(SecretKind::Join, &payload).unwrap();
+//!
+//! assert_eq!(signer.verify(SecretKind::Join, &join).unwrap(), payload);
+//! assert!(signer.verify(SecretKind::Spectate, &join).is_err());
+//! ```
+use std::time::Duration;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::{unix_time, Error, Result};
+
+/// The maximum length of an activity secret accepted by Discord.
+pub const MAX_SECRET_LENGTH: usize = 128;
+
+/// The length of the truncated HMAC-SHA256 tag, in bytes.
+const TAG_LENGTH: usize = 16;
+
+/// Which secret of an activity a secret is for.
+///
+/// The kind is part of the signature, so that a spectate secret cannot
+/// be used to join.
+#[allow(missing_docs)]
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum SecretKind {
+    Join,
+    Spectate,
+    Match,
+}
+
+impl SecretKind {
+    fn tag(self) -> &'static [u8] {
+        match self {
+            SecretKind::Join => b"join",
+            SecretKind::Spectate => b"spectate",
+            SecretKind::Match => b"match",
+        }
+    }
+}
+
+/// The data contained in a signed secret.
+///
+/// Note that all methods return `Self`, and can be chained
+/// for fluency
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct SecretPayload {
+    /// The ID of the party the secret is for.
+    #[serde(rename = "p")]
+    pub party_id: String,
+    /// The address of the server hosting the party, if any.
+    #[serde(rename = "s", skip_serializing_if = "Option::is_none", default)]
+    pub server: Option<String>,
+    /// When the secret expires, in seconds since the Unix epoch.
+    #[serde(rename = "e")]
+    pub expires_at: u64,
+}
+
+impl SecretPayload {
+    /// Creates a new `SecretPayload` for a party, expiring after `expires_in`.
+    pub fn new(party_id: &str, expires_in: Duration) -> Self {
+        Self {
+            party_id: party_id.to_string(),
+            server: None,
+            expires_at: unix_time().saturating_add(expires_in.as_secs()),
+        }
+    }
+
+    /// Sets the address of the server hosting the party
+    pub fn server(mut self, server: &str) -> Self {
+        self.server = Some(server.to_string());
+        self
+    }
+
+    /// Whether the secret has expired.
+    pub fn is_expired(&self) -> bool {
+        unix_time() >= self.expires_at
+    }
+}
+
+/// Signs and verifies activity secrets with an application key.
+#[derive(Clone)]
+pub struct SecretSigner {
+    key: Vec<u8>,
+}
+
+impl SecretSigner {
+    /// Creates a new `SecretSigner` with the given key.
+    ///
+    /// The key must be kept secret, and should be at least 32 random bytes.
+    pub fn new(key: &[u8]) -> Self {
+        Self { key: key.to_vec() }
+    }
+
+    /// Encodes and signs a payload as a secret of the given kind.
+    ///
+    /// # Errors
+    /// Returns [`Error::SecretTooLong`] if the secret is longer than
+    /// [`MAX_SECRET_LENGTH`].
+    pub fn sign(&self, kind: SecretKind, payload: &SecretPayload) -> Result<String> {
+        let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(payload)?);
+        let tag = URL_SAFE_NO_PAD.encode(&self.mac(kind, &payload).finalize().into_bytes()[..TAG_LENGTH]);
+
+        let secret = format!("{}.{}", payload, tag);
+        if secret.len() > MAX_SECRET_LENGTH {
+            return Err(Error::SecretTooLong(secret.len()));
+        }
+
+        Ok(secret)
+    }
+
+    /// Verifies a secret of the given kind, and decodes its payload.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidSecret`] if the secret is malformed, was not
+    /// signed with this key, or is of another kind, and [`Error::SecretExpired`]
+    /// if the secret has expired.
+    pub fn verify(&self, kind: SecretKind, secret: &str) -> Result<SecretPayload> {
+        let (payload, tag) = secret.split_once('.').ok_or(Error::InvalidSecret)?;
+        let tag = URL_SAFE_NO_PAD.decode(tag).map_err(|_| Error::InvalidSecret)?;
+        if tag.len() != TAG_LENGTH {
+            return Err(Error::InvalidSecret);
+        }
+        self.mac(kind, payload).verify_truncated_left(&tag).map_err(|_| Error::InvalidSecret)?;
+
+        let payload = URL_SAFE_NO_PAD.decode(payload).map_err(|_| Error::InvalidSecret)?;
+        let payload: SecretPayload = serde_json::from_slice(&payload).map_err(|_| Error::InvalidSecret)?;
+        if payload.is_expired() {
+            return Err(Error::SecretExpired);
+        }
+
+        Ok(payload)
+    }
+
+    fn mac(&self, kind: SecretKind, payload: &str) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+        mac.update(kind.tag());
+        mac.update(b".");
+        mac.update(payload.as_bytes());
+        mac
+    }
+}
+
+impl std::fmt::Debug for SecretSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("SecretSigner").finish_non_exhaustive()
+    }
+}
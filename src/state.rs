@@ -0,0 +1,109 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use serde_json::Value;
+
+use crate::{ActivityEvent, DiscordIPCCommandIncoming, models::{Image, ImageFormat, OverlayState, Scope, Snowflake, StoredToken}, oauth2::{TokenExchanger, TokenStore}};
+
+/// The state of a connection to the Discord IPC, shared by every
+/// [`DiscordIpc`](crate::DiscordIpc) implementation.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct ClientState {
+    /// Events received while waiting for the response to a command.
+    pub(crate) events: VecDeque<DiscordIPCCommandIncoming>,
+    /// The events subscribed to in the current session, with their arguments
+    /// and the number of times they were subscribed to.
+    pub(crate) subscriptions: Vec<(ActivityEvent, Value, usize)>,
+    /// The scopes the current session is authenticated with.
+    pub(crate) scopes: Vec<Scope>,
+    /// Exchanges authorization codes and refresh tokens for access tokens.
+    pub(crate) token_exchanger: Option<Box<dyn TokenExchanger>>,
+    /// The access token of the current session.
+    pub(crate) token: Option<StoredToken>,
+    /// Persists the access token between sessions.
+    pub(crate) token_store: Option<Box<dyn TokenStore>>,
+    /// The scopes to authorize when there is no valid stored token.
+    pub(crate) login_scopes: Vec<Scope>,
+    /// The last known state of the overlay.
+    pub(crate) overlay: Option<OverlayState>,
+    /// The most recently fetched avatars.
+    pub(crate) avatars: ImageCache,
+}
+
+impl ClientState {
+    /// Counts a subscription to an event.
+    pub(crate) fn hold_subscription(&mut self, evt: ActivityEvent, args: Value) {
+        match self.subscriptions.iter_mut().find(|(e, a, _)| *e == evt && *a == args) {
+            Some((_, _, count)) => *count += 1,
+            None => self.subscriptions.push((evt, args, 1)),
+        }
+    }
+
+    /// Releases a subscription to an event, and returns whether Discord should
+    /// be unsubscribed from it, which is when it is no longer held at all.
+    pub(crate) fn release_subscription(&mut self, evt: ActivityEvent, args: &Value) -> bool {
+        let Some(index) = self.subscriptions.iter().position(|(e, a, _)| *e == evt && a == args) else {
+            return true;
+        };
+        self.subscriptions[index].2 -= 1;
+        if self.subscriptions[index].2 > 0 {
+            return false;
+        }
+        self.subscriptions.remove(index);
+
+        true
+    }
+}
+
+/// The key of an avatar in an [`ImageCache`]: the ID of the user, the size and the format.
+pub(crate) type ImageKey = (Snowflake, u16, ImageFormat);
+
+/// A least recently used cache of images.
+pub(crate) struct ImageCache {
+    /// The images, from least to most recently used.
+    entries: VecDeque<(ImageKey, Arc<Image>)>,
+    capacity: usize,
+}
+
+impl ImageCache {
+    /// The number of images kept by default.
+    pub const DEFAULT_CAPACITY: usize = 64;
+
+    /// Gets an image, marking it as the most recently used.
+    pub fn get(&mut self, key: &ImageKey) -> Option<Arc<Image>> {
+        let index = self.entries.iter().position(|(k, _)| k == key)?;
+        let entry = self.entries.remove(index)?;
+        let image = entry.1.clone();
+        self.entries.push_back(entry);
+
+        Some(image)
+    }
+
+    /// Inserts an image, evicting the least recently used images if the cache is full.
+    pub fn insert(&mut self, key: ImageKey, image: Arc<Image>) {
+        self.entries.retain(|(k, _)| *k != key);
+        self.entries.push_back((key, image));
+        self.shrink();
+    }
+
+    /// Sets the number of images kept, evicting the least recently used images if needed.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.shrink();
+    }
+
+    fn shrink(&mut self) {
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+    }
+}
+
+impl Default for ImageCache {
+    fn default() -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity: Self::DEFAULT_CAPACITY,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use crate::{ActivityEvent, DiscordIpc, Result};
+
+/// A subscription to an event that is not scoped to a channel, guild or
+/// lobby, held by the helpers handling that event, such as
+/// [`NotificationBridge`](crate::notifications::NotificationBridge).
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Subscription {
+    evt: ActivityEvent,
+    subscribed: bool,
+}
+
+impl Subscription {
+    /// Creates a new `Subscription` to `evt`, without subscribing to it.
+    pub fn new(evt: ActivityEvent) -> Self {
+        Self { evt, subscribed: false }
+    }
+
+    /// Creates a new `Subscription`, subscribing to `evt`.
+    pub fn subscribe<C: DiscordIpc>(client: &mut C, evt: ActivityEvent) -> Result<Self> {
+        client.subscribe(evt, crate::Empty)?;
+
+        Ok(Self { evt, subscribed: true })
+    }
+
+    /// Unsubscribes from the event, if it was subscribed to.
+    pub fn unsubscribe<C: DiscordIpc>(self, client: &mut C) -> Result<()> {
+        if self.subscribed {
+            client.unsubscribe(self.evt, crate::Empty)?;
+        }
+
+        Ok(())
+    }
+}
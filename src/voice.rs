@@ -0,0 +1,369 @@
+//! Provides helpers that keep track of the user's voice channel and
+//! voice connection from the voice events dispatched by Discord.
+use std::collections::VecDeque;
+
+use serde::Serialize;
+
+use crate::{
+    events::Event,
+    models::{Snowflake, VoiceConnectionState, VoiceConnectionStatus, VoiceState},
+    subscription::Subscription,
+    ActivityEvent, DiscordIpc, Result,
+};
+
+/// The arguments of subscriptions to the events of a channel.
+#[derive(Serialize, Debug)]
+struct ChannelSubscription {
+    channel_id: Snowflake,
+}
+
+/// The events of a voice channel, subscribed to for every channel the user joins.
+const CHANNEL_EVENTS: [ActivityEvent; 5] = [
+    ActivityEvent::VoiceStateCreate,
+    ActivityEvent::VoiceStateUpdate,
+    ActivityEvent::VoiceStateDelete,
+    ActivityEvent::SpeakingStart,
+    ActivityEvent::SpeakingStop,
+];
+
+/// A live model of the voice channel the user is in: who is in it, who is
+/// speaking, and whether they are muted or deafened.
+///
+/// The roster is kept up to date by passing every received event to
+/// [`handle_event`](VoiceRoster::handle_event). When the user moves to another
+/// voice channel, the roster subscribes to the events of that channel instead.
+///
+/// Requires the [`Rpc`](crate::models::Scope::Rpc) scope.
+///
+/// # Examples
+/// ```no_run
+/// # use discord_ipc_rp::{voice::VoiceRoster, DiscordIpc, DiscordIpcClient};
+/// # fn main() -> discord_ipc_rp::Result<()> {
+/// # let mut client = DiscordIpcClient::new("771124766517755954")?;
+/// let mut roster = VoiceRoster::subscribe(&mut client)?;
+/// loop {
+///     let event = client.recv_event()?;
+///     if roster.handle_event(&mut client, &event)? {
+///         for member in roster.members() {
+///             println!("{} (speaking: {})", member.user.username, roster.is_speaking(member.user.id));
+///         }
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct VoiceRoster {
+    channel_id: Option<Snowflake>,
+    guild_id: Option<Snowflake>,
+    members: Vec<VoiceState>,
+    speaking: Vec<Snowflake>,
+    connection_state: Option<VoiceConnectionState>,
+}
+
+impl VoiceRoster {
+    /// Creates a new `VoiceRoster`, subscribing to the voice events and
+    /// loading the voice channel the user is currently in.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if subscribing or loading the channel failed.
+    pub fn subscribe<C: DiscordIpc>(client: &mut C) -> Result<Self> {
+        client.subscribe(ActivityEvent::VoiceChannelSelect, crate::Empty)?;
+        client.subscribe(ActivityEvent::VoiceConnectionStatus, crate::Empty)?;
+
+        let mut roster = Self::default();
+        if let Some(channel) = client.get_selected_voice_channel()? {
+            roster.join(client, channel.id, channel.guild_id)?;
+            roster.members = channel.voice_states;
+        }
+
+        Ok(roster)
+    }
+
+    /// Unsubscribes from all of the events the roster subscribed to.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if unsubscribing failed.
+    pub fn unsubscribe<C: DiscordIpc>(mut self, client: &mut C) -> Result<()> {
+        self.leave(client)?;
+        client.unsubscribe(ActivityEvent::VoiceChannelSelect, crate::Empty)?;
+        client.unsubscribe(ActivityEvent::VoiceConnectionStatus, crate::Empty)
+    }
+
+    /// Updates the roster from an event, and returns whether it changed.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if the user moved to another voice channel,
+    /// and subscribing to its events failed.
+    pub fn handle_event<C: DiscordIpc>(&mut self, client: &mut C, event: &Event) -> Result<bool> {
+        match event {
+            Event::VoiceChannelSelect(select) if select.channel_id != self.channel_id => {
+                self.leave(client)?;
+                if let Some(channel_id) = select.channel_id {
+                    self.join(client, channel_id, select.guild_id)?;
+                    self.members = client.get_channel(channel_id)?.voice_states;
+                }
+            }
+            Event::VoiceStateCreate(state) | Event::VoiceStateUpdate(state) if self.channel_id.is_some() => {
+                match self.members.iter_mut().find(|member| member.user.id == state.user.id) {
+                    Some(member) => *member = state.clone(),
+                    None => self.members.push(state.clone()),
+                }
+            }
+            Event::VoiceStateDelete(state) if self.channel_id.is_some() => {
+                self.members.retain(|member| member.user.id != state.user.id);
+                self.speaking.retain(|user_id| *user_id != state.user.id);
+            }
+            Event::SpeakingStart(speaking) if self.channel_id.is_some() && !self.is_speaking(speaking.user_id) => {
+                self.speaking.push(speaking.user_id);
+            }
+            Event::SpeakingStop(speaking) if self.is_speaking(speaking.user_id) => {
+                self.speaking.retain(|user_id| *user_id != speaking.user_id);
+            }
+            Event::VoiceConnectionStatus(status) if self.connection_state != Some(status.state) => {
+                self.connection_state = Some(status.state);
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    /// The ID of the voice channel the user is in, if any.
+    pub fn channel_id(&self) -> Option<Snowflake> {
+        self.channel_id
+    }
+
+    /// The ID of the guild of the voice channel the user is in, if any.
+    pub fn guild_id(&self) -> Option<Snowflake> {
+        self.guild_id
+    }
+
+    /// The voice states of the users in the channel, in the order they joined.
+    pub fn members(&self) -> impl Iterator<Item = &VoiceState> {
+        self.members.iter()
+    }
+
+    /// The voice state of a user in the channel.
+    pub fn member(&self, user_id: Snowflake) -> Option<&VoiceState> {
+        self.members.iter().find(|member| member.user.id == user_id)
+    }
+
+    /// The IDs of the users that are currently speaking.
+    pub fn speaking(&self) -> impl Iterator<Item = Snowflake> + '_ {
+        self.speaking.iter().copied()
+    }
+
+    /// Whether a user is currently speaking.
+    pub fn is_speaking(&self, user_id: Snowflake) -> bool {
+        self.speaking.contains(&user_id)
+    }
+
+    /// Whether a user in the channel is muted, either by themselves or by the guild.
+    pub fn is_muted(&self, user_id: Snowflake) -> bool {
+        self.member(user_id).is_some_and(|member| member.voice_state.mute || member.voice_state.self_mute)
+    }
+
+    /// Whether a user in the channel is deafened, either by themselves or by the guild.
+    pub fn is_deafened(&self, user_id: Snowflake) -> bool {
+        self.member(user_id).is_some_and(|member| member.voice_state.deaf || member.voice_state.self_deaf)
+    }
+
+    /// The state of the user's voice connection, once it is known.
+    pub fn connection_state(&self) -> Option<VoiceConnectionState> {
+        self.connection_state
+    }
+
+    fn join<C: DiscordIpc>(&mut self, client: &mut C, channel_id: Snowflake, guild_id: Option<Snowflake>) -> Result<()> {
+        for evt in CHANNEL_EVENTS {
+            client.subscribe(evt, ChannelSubscription { channel_id })?;
+        }
+        self.channel_id = Some(channel_id);
+        self.guild_id = guild_id;
+
+        Ok(())
+    }
+
+    fn leave<C: DiscordIpc>(&mut self, client: &mut C) -> Result<()> {
+        if let Some(channel_id) = self.channel_id.take() {
+            for evt in CHANNEL_EVENTS {
+                client.unsubscribe(evt, ChannelSubscription { channel_id })?;
+            }
+        }
+        self.guild_id = None;
+        self.members.clear();
+        self.speaking.clear();
+
+        Ok(())
+    }
+}
+
+/// A change of the state of the user's voice connection.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct VoiceConnectionTransition {
+    /// The previous state, or `None` if it was not known.
+    pub from: Option<VoiceConnectionState>,
+    /// The new state.
+    pub to: VoiceConnectionState,
+}
+
+/// A summary of the latest pings to the voice server, in milliseconds.
+#[derive(Clone, PartialEq, Debug)]
+pub struct LatencySummary {
+    /// The lowest ping.
+    pub min: f64,
+    /// The average ping.
+    pub avg: f64,
+    /// The highest ping.
+    pub max: f64,
+    /// The latest pings, from oldest to newest.
+    pub pings: Vec<f64>,
+}
+
+impl LatencySummary {
+    /// The most recent ping.
+    pub fn last(&self) -> f64 {
+        self.pings.last().copied().unwrap_or_default()
+    }
+}
+
+/// Monitors the quality of the user's voice connection, from
+/// [`VoiceConnectionStatus`](crate::ActivityEvent::VoiceConnectionStatus) events.
+///
+/// The monitor reports every change of the connection state, and keeps the
+/// latest pings to summarize the latency of the connection.
+///
+/// Requires the [`Rpc`](crate::models::Scope::Rpc) scope.
+///
+/// # Examples
+/// ```no_run
+/// # use discord_ipc_rp::{voice::VoiceConnectionMonitor, DiscordIpc, DiscordIpcClient};
+/// # fn main() -> discord_ipc_rp::Result<()> {
+/// # let mut client = DiscordIpcClient::new("771124766517755954")?;
+/// let mut monitor = VoiceConnectionMonitor::subscribe(&mut client, 20)?;
+/// loop {
+///     let event = client.recv_event()?;
+///     if let Some(transition) = monitor.handle_event(&event) {
+///         println!("voice connection: {:?} -> {}", transition.from, transition.to);
+///     }
+///     if monitor.latency().is_some_and(|latency| latency.avg > 250.0) {
+///         println!("voice connection degraded");
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct VoiceConnectionMonitor {
+    state: Option<VoiceConnectionState>,
+    hostname: Option<String>,
+    pings: VecDeque<f64>,
+    last_ping_time: Option<u64>,
+    capacity: usize,
+    subscription: Subscription,
+}
+
+impl VoiceConnectionMonitor {
+    /// Creates a new `VoiceConnectionMonitor` keeping the latest `capacity`
+    /// pings, without subscribing to any events.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: None,
+            hostname: None,
+            pings: VecDeque::with_capacity(capacity),
+            last_ping_time: None,
+            capacity,
+            subscription: Subscription::new(ActivityEvent::VoiceConnectionStatus),
+        }
+    }
+
+    /// Creates a new `VoiceConnectionMonitor` keeping the latest `capacity`
+    /// pings, and subscribes to the voice connection events.
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if subscribing failed.
+    pub fn subscribe<C: DiscordIpc>(client: &mut C, capacity: usize) -> Result<Self> {
+        let subscription = Subscription::subscribe(client, ActivityEvent::VoiceConnectionStatus)?;
+
+        Ok(Self { subscription, ..Self::new(capacity) })
+    }
+
+    /// Unsubscribes from the voice connection events, if the monitor was
+    /// created with [`subscribe`](Self::subscribe).
+    ///
+    /// # Errors
+    /// Returns an `Err` variant if unsubscribing failed.
+    pub fn unsubscribe<C: DiscordIpc>(self, client: &mut C) -> Result<()> {
+        self.subscription.unsubscribe(client)
+    }
+
+    /// Updates the monitor from an event, and returns the state transition
+    /// if the state of the connection changed.
+    pub fn handle_event(&mut self, event: &Event) -> Option<VoiceConnectionTransition> {
+        match event {
+            Event::VoiceConnectionStatus(status) => self.update(status),
+            _ => None,
+        }
+    }
+
+    /// Updates the monitor from the status of the voice connection, and
+    /// returns the state transition if the state of the connection changed.
+    pub fn update(&mut self, status: &VoiceConnectionStatus) -> Option<VoiceConnectionTransition> {
+        self.hostname.clone_from(&status.hostname);
+
+        // timed pings are the full history, so only the new ones are recorded
+        let timed = status.pings.iter().filter_map(|ping| ping.time.map(|time| (time, ping.value)));
+        let mut recorded = false;
+        for (time, value) in timed {
+            recorded = true;
+            if self.last_ping_time.is_none_or(|last| time > last) {
+                self.last_ping_time = Some(time);
+                self.record(value);
+            }
+        }
+        if let (false, Some(last_ping)) = (recorded, status.last_ping) {
+            self.record(last_ping);
+        }
+
+        if self.state == Some(status.state) {
+            return None;
+        }
+        let transition = VoiceConnectionTransition { from: self.state, to: status.state };
+        self.state = Some(status.state);
+
+        Some(transition)
+    }
+
+    /// The state of the voice connection, once it is known.
+    pub fn state(&self) -> Option<VoiceConnectionState> {
+        self.state
+    }
+
+    /// The hostname of the voice server the user is connected to.
+    pub fn hostname(&self) -> Option<&str> {
+        self.hostname.as_deref()
+    }
+
+    /// A summary of the latest pings, or `None` if there are none yet.
+    pub fn latency(&self) -> Option<LatencySummary> {
+        if self.pings.is_empty() {
+            return None;
+        }
+
+        let pings: Vec<f64> = self.pings.iter().copied().collect();
+        Some(LatencySummary {
+            min: pings.iter().copied().fold(f64::INFINITY, f64::min),
+            avg: pings.iter().sum::<f64>() / pings.len() as f64,
+            max: pings.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            pings,
+        })
+    }
+
+    fn record(&mut self, ping: f64) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.pings.len() == self.capacity {
+            self.pings.pop_front();
+        }
+        self.pings.push_back(ping);
+    }
+}
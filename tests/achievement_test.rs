@@ -0,0 +1,74 @@
+mod common;
+
+use common::{error, event, reply, MockIpc};
+use discord_ipc_rp::{
+    achievements::AchievementWatcher,
+    models::{AchievementProgress, Snowflake, UserAchievement},
+    DiscordIpc, Error, Result,
+};
+use serde_json::{json, Value};
+
+const ACHIEVEMENT: &str = "853169925467193345";
+
+fn achievement(percent_complete: u8) -> Value {
+    json!({
+        "user_id": "53908232506183680",
+        "achievement_id": ACHIEVEMENT,
+        "percent_complete": percent_complete,
+        "unlocked_at": if percent_complete == 100 { json!("2024-01-01T12:00:00.000000+00:00") } else { Value::Null },
+    })
+}
+
+#[test]
+fn test_achievements() -> Result<()> {
+    let mut client = MockIpc::new(|request| match request["cmd"].as_str() {
+        Some("GET_USER_ACHIEVEMENTS") => vec![reply(request, json!([achievement(100)]))],
+        Some("SET_USER_ACHIEVEMENT") if request["args"]["achievement_id"] == ACHIEVEMENT => vec![reply(request, Value::Null)],
+        _ => vec![error(request, 5010, "Unauthorized for achievement")],
+    });
+    client.connect()?;
+
+    let id: Snowflake = ACHIEVEMENT.parse()?;
+    client.set_user_achievement(id, &AchievementProgress::new(50).metadata("level", "3"))?;
+    client.set_user_achievement(id, &AchievementProgress::new(150))?;
+    assert!(matches!(
+        client.set_user_achievement(Snowflake::new(1), &AchievementProgress::new(10)),
+        Err(Error::UnauthorizedForAchievement)
+    ));
+
+    let achievements = client.get_user_achievements()?;
+    assert_eq!(achievements[0].percent_complete, 100);
+    assert!(achievements[0].unlocked_at.is_some());
+
+    let commands = client.commands();
+    assert_eq!(commands[0]["args"], json!({ "achievement_id": ACHIEVEMENT, "percent_complete": 50, "metadata": { "level": "3" } }));
+    assert_eq!(commands[1]["args"], json!({ "achievement_id": ACHIEVEMENT, "percent_complete": 100 }));
+
+    Ok(())
+}
+
+#[test]
+fn test_achievement_watcher() -> Result<()> {
+    let mut client = MockIpc::with_subscriptions(|_| vec![]);
+    client.connect()?;
+
+    let mut updates = Vec::new();
+    let mut watcher = AchievementWatcher::subscribe(&mut client, |achievement: &UserAchievement| {
+        updates.push(achievement.percent_complete);
+    })?;
+
+    client.push(1, event("USER_ACHIEVEMENT_UPDATE", achievement(40)));
+    client.push(1, event("VOICE_CHANNEL_SELECT", json!({ "channel_id": null })));
+    client.push(1, event("USER_ACHIEVEMENT_UPDATE", achievement(100)));
+    for _ in 0..3 {
+        watcher.handle_event(&client.recv_event()?);
+    }
+    watcher.unsubscribe(&mut client)?;
+
+    assert_eq!(updates, [40, 100]);
+    let commands = client.commands();
+    assert_eq!((&commands[0]["cmd"], &commands[0]["evt"]), (&json!("SUBSCRIBE"), &json!("USER_ACHIEVEMENT_UPDATE")));
+    assert_eq!((&commands[1]["cmd"], &commands[1]["evt"]), (&json!("UNSUBSCRIBE"), &json!("USER_ACHIEVEMENT_UPDATE")));
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::{event, reply, user, MockIpc};
+use discord_ipc_rp::{
+    events::Event,
+    models::{ActivityActionType, ActivityType, Snowflake},
+    DiscordIpc, Result,
+};
+use serde_json::{json, Value};
+
+fn client() -> MockIpc {
+    MockIpc::with_subscriptions(|request| vec![reply(request, Value::Null)])
+}
+
+#[test]
+fn test_activity_events() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+    client.subscribe_activity_events()?;
+
+    let subscribed: Vec<_> = client.commands().iter().map(|command| command["evt"].clone()).collect();
+    assert_eq!(subscribed, ["ACTIVITY_JOIN", "ACTIVITY_SPECTATE", "ACTIVITY_JOIN_REQUEST", "ACTIVITY_INVITE"]);
+
+    client.push(1, event("ACTIVITY_JOIN", json!({ "secret": "join-secret" })));
+    client.push(1, event("ACTIVITY_SPECTATE", json!({ "secret": "spectate-secret" })));
+    client.push(1, event("ACTIVITY_JOIN_REQUEST", json!({ "user": user("53908232506183680", "mason") })));
+
+    assert!(matches!(client.recv_event()?, Event::ActivityJoin(join) if join.secret == "join-secret"));
+    assert!(matches!(client.recv_event()?, Event::ActivitySpectate(spectate) if spectate.secret == "spectate-secret"));
+    let Event::ActivityJoinRequest(request) = client.recv_event()? else { panic!("expected a join request") };
+
+    client.send_activity_join_invite(request.user.id)?;
+    client.close_activity_join_request(request.user.id)?;
+
+    let commands = client.commands();
+    assert_eq!(commands[4]["cmd"], "SEND_ACTIVITY_JOIN_INVITE");
+    assert_eq!(commands[4]["args"], json!({ "user_id": "53908232506183680" }));
+    assert_eq!(commands[5]["cmd"], "CLOSE_ACTIVITY_JOIN_REQUEST");
+
+    Ok(())
+}
+
+#[test]
+fn test_activity_invites() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    client.activity_invite_user(Snowflake::new(53908232506183680), ActivityActionType::Join, "Join my party!")?;
+    let args = &client.commands()[0]["args"];
+    assert_eq!(args["type"], 1);
+    assert_eq!(args["content"], "Join my party!");
+    assert_eq!(args["pid"], std::process::id());
+
+    client.push(
+        1,
+        event(
+            "ACTIVITY_INVITE",
+            json!({
+                "type": 1,
+                "user": user("53908232506183680", "mason"),
+                "activity": {
+                    "name": "Game", "type": 0, "application_id": "771124766517755954", "session_id": "abc",
+                    "party": { "id": "party", "size": [1, 4] }
+                },
+                "channel_id": "199737254929760256",
+                "message_id": "199737254929760257"
+            }),
+        ),
+    );
+    let Event::ActivityInvite(invite) = client.recv_event()? else { panic!("expected an invite") };
+    assert_eq!(invite.activity.kind, ActivityType::Playing);
+    assert_eq!(invite.activity.party.as_ref().unwrap().size, Some([1, 4]));
+
+    client.accept_activity_invite(&invite)?;
+    assert_eq!(
+        client.commands()[1]["args"],
+        json!({
+            "type": 1, "user_id": "53908232506183680", "session_id": "abc",
+            "channel_id": "199737254929760256", "message_id": "199737254929760257"
+        })
+    );
+
+    // action types added by Discord later still deserialize
+    let kind: ActivityActionType = serde_json::from_value(json!(3))?;
+    assert_eq!(kind, ActivityActionType::Unknown);
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use common::{error, event, reply, user, MockIpc};
+use discord_ipc_rp::{
+    models::Scope, DiscordIPCErrorCode, DiscordIPCErrorCodeNonCritical, DiscordIpc, Error,
+};
+use serde_json::json;
+
+fn authenticate_response() -> serde_json::Value {
+    json!({
+        "access_token": "token",
+        "user": user("53908232506183680", "mason"),
+        "scopes": ["rpc", "identify"],
+        "expires": "2017-05-08T20:19:55.433000+00:00",
+        "application": { "id": "771124766517755954", "name": "Test", "description": "", "icon": null, "rpc_origins": [] }
+    })
+}
+
+#[test]
+fn test_authorize() -> discord_ipc_rp::Result<()> {
+    let mut client = MockIpc::new(|request| match request["cmd"].as_str() {
+        Some("AUTHORIZE") => vec![
+            event("ACTIVITY_JOIN", json!({ "secret": "x" })),
+            reply(request, json!({ "code": "abc" })),
+        ],
+        _ => vec![],
+    });
+    client.connect()?;
+
+    let code = client.authorize(&[Scope::Rpc, Scope::RpcVoiceRead], None)?;
+    assert_eq!(code, "abc");
+
+    let sent = client.commands()[0];
+    assert_eq!(sent["args"], json!({ "client_id": "771124766517755954", "scopes": ["rpc", "rpc.voice.read"] }));
+    assert!(sent.get("evt").is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_authenticate_tracks_scopes() -> discord_ipc_rp::Result<()> {
+    let mut client = MockIpc::new(|request| match request["cmd"].as_str() {
+        Some("AUTHENTICATE") => vec![reply(request, authenticate_response())],
+        _ => vec![],
+    });
+    client.connect()?;
+    assert!(client.scopes().is_empty());
+
+    let response = client.authenticate("token")?;
+    assert_eq!(response.user.username, "mason");
+    assert_eq!(response.user.id.get(), 53908232506183680);
+    assert_eq!(client.scopes(), &[Scope::Rpc, Scope::Identify]);
+    assert!(client.has_scope(Scope::Identify));
+    assert!(!client.has_scope(Scope::RpcVoiceWrite));
+
+    client.reconnect()?;
+    assert!(client.scopes().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_authenticate_error() -> discord_ipc_rp::Result<()> {
+    let mut client = MockIpc::new(|request| vec![error(request, 4009, "Invalid token")]);
+    client.connect()?;
+
+    match client.authenticate("bad") {
+        Err(Error::Discord(error)) => {
+            assert_eq!(error.code, DiscordIPCErrorCode::NonCritical(DiscordIPCErrorCodeNonCritical::InvalidToken));
+            assert_eq!(error.message, "Invalid token");
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(client.scopes().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_authenticate_with_unknown_scope() -> discord_ipc_rp::Result<()> {
+    let mut client = MockIpc::new(|request| {
+        let mut response = authenticate_response();
+        response["scopes"] = json!(["rpc", "rpc.video.read", "openid"]);
+        vec![reply(request, response)]
+    });
+    client.connect()?;
+
+    client.authenticate("token")?;
+    assert_eq!(client.scopes(), &[Scope::Rpc, Scope::Unknown, Scope::Unknown]);
+    assert!(client.has_scope(Scope::Rpc));
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use common::{reply, MockIpc};
+use discord_ipc_rp::{
+    models::{Image, ImageFormat, Snowflake},
+    DiscordIpc, Error, Result,
+};
+use serde_json::json;
+
+const PNG: &str = "data:image/png;base64,iVBORw0KGgo=";
+
+fn client() -> MockIpc {
+    MockIpc::new(|request| match request["args"]["id"].as_str() {
+        Some("1") => vec![reply(request, json!({ "data_url": "not a data uri" }))],
+        _ => vec![reply(request, json!({ "data_url": PNG }))],
+    })
+}
+
+fn fetches(client: &MockIpc) -> usize {
+    client.commands().iter().filter(|command| command["cmd"] == "GET_IMAGE").count()
+}
+
+#[test]
+fn test_get_user_avatar() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    let user_id = Snowflake::new(53908232506183680);
+    let avatar = client.get_user_avatar(user_id, 128, ImageFormat::Png)?;
+    assert_eq!(avatar.mime_type, "image/png");
+    assert_eq!(avatar.data, b"\x89PNG\r\n\x1a\n");
+    assert_eq!(
+        client.commands()[0]["args"],
+        json!({ "type": "user", "id": "53908232506183680", "format": "png", "size": 128 })
+    );
+
+    client.get_user_avatar(user_id, 128, ImageFormat::Png)?;
+    assert_eq!(fetches(&client), 1);
+    client.get_user_avatar(user_id, 64, ImageFormat::Png)?;
+    assert_eq!(fetches(&client), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_avatar_cache_evicts_least_recently_used() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+    client.set_avatar_cache_capacity(2);
+
+    let (a, b, c) = (Snowflake::new(10), Snowflake::new(11), Snowflake::new(12));
+    client.get_user_avatar(a, 64, ImageFormat::Png)?;
+    client.get_user_avatar(b, 64, ImageFormat::Png)?;
+    client.get_user_avatar(a, 64, ImageFormat::Png)?;
+    client.get_user_avatar(c, 64, ImageFormat::Png)?;
+    assert_eq!(fetches(&client), 3);
+
+    client.get_user_avatar(a, 64, ImageFormat::Png)?;
+    assert_eq!(fetches(&client), 3);
+    client.get_user_avatar(b, 64, ImageFormat::Png)?;
+    assert_eq!(fetches(&client), 4);
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_avatars() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    assert!(matches!(client.get_user_avatar(Snowflake::new(2), 100, ImageFormat::Png), Err(Error::InvalidImageSize(100))));
+    assert!(matches!(client.get_user_avatar(Snowflake::new(2), 2048, ImageFormat::Png), Err(Error::InvalidImageSize(2048))));
+    assert!(matches!(client.get_user_avatar(Snowflake::new(1), 64, ImageFormat::Png), Err(Error::InvalidImage)));
+    assert!(matches!(Image::from_data_uri("data:image/png,raw"), Err(Error::InvalidImage)));
+    assert_eq!(fetches(&client), 1);
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use common::{error, event, reply, MockIpc};
+use discord_ipc_rp::{events::Event, models::KeyType, DiscordIpc, Error, Result};
+use serde_json::{json, Value};
+
+#[test]
+fn test_capture_shortcut() -> Result<()> {
+    let mut client = MockIpc::new(|request| vec![reply(request, Value::Null)]);
+    client.connect()?;
+
+    client.start_capture_shortcut()?;
+    client.push(1, event("CAPTURE_SHORTCUT_CHANGE", json!({ "shortcut": [{ "type": 2, "code": 162, "name": "left ctrl" }] })));
+    client.push(
+        1,
+        event(
+            "CAPTURE_SHORTCUT_CHANGE",
+            json!({ "shortcut": [{ "type": 2, "code": 162, "name": "left ctrl" }, { "type": 1, "code": 4, "name": "mouse4" }] }),
+        ),
+    );
+    client.recv_event()?;
+    let Event::CaptureShortcutChange(change) = client.recv_event()? else { panic!("expected a shortcut change") };
+    client.stop_capture_shortcut()?;
+
+    assert_eq!(change.shortcut.len(), 2);
+    assert_eq!(change.shortcut[0].kind, KeyType::KeyboardModifierKey);
+    assert_eq!((change.shortcut[1].kind, change.shortcut[1].code, change.shortcut[1].name.as_str()), (KeyType::MouseButton, 4, "mouse4"));
+
+    let commands = client.commands();
+    assert_eq!(commands[0]["cmd"], "CAPTURE_SHORTCUT");
+    assert_eq!(commands[0]["args"], json!({ "action": "START" }));
+    assert_eq!(commands[1]["args"], json!({ "action": "STOP" }));
+
+    Ok(())
+}
+
+#[test]
+fn test_capture_shortcut_already_listening() -> Result<()> {
+    let mut client = MockIpc::new(|request| vec![error(request, 5004, "Already listening")]);
+    client.connect()?;
+
+    assert!(matches!(client.start_capture_shortcut(), Err(Error::CaptureShortcutAlreadyListening)));
+
+    Ok(())
+}
@@ -0,0 +1,147 @@
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+
+use discord_ipc_rp::{models::Snowflake, ClientState, DiscordIpc, Result};
+use serde_json::{json, Value};
+
+type Responder = Box<dyn FnMut(&Value) -> Vec<Value>>;
+
+/// An in-memory stand-in for the Discord IPC socket.
+///
+/// Every frame written by the client is recorded, and passed to the
+/// responder to produce the frames Discord would send back.
+pub struct MockIpc {
+    client_id: Snowflake,
+    state: ClientState,
+    written: Vec<u8>,
+    incoming: VecDeque<u8>,
+    responder: Responder,
+    /// Every frame sent by the client, as `(opcode, payload)`.
+    pub sent: Vec<(u32, Value)>,
+    /// The number of times the connection was closed.
+    pub closed: usize,
+}
+
+impl MockIpc {
+    pub fn new(responder: impl FnMut(&Value) -> Vec<Value> + 'static) -> Self {
+        Self {
+            client_id: Snowflake::new(771124766517755954),
+            state: ClientState::default(),
+            written: Vec::new(),
+            incoming: VecDeque::new(),
+            responder: Box::new(responder),
+            sent: Vec::new(),
+            closed: 0,
+        }
+    }
+
+    /// Creates a client whose `SUBSCRIBE` and `UNSUBSCRIBE` commands succeed,
+    /// passing every other command to the responder.
+    pub fn with_subscriptions(mut responder: impl FnMut(&Value) -> Vec<Value> + 'static) -> Self {
+        Self::new(move |request| match request["cmd"].as_str() {
+            Some("SUBSCRIBE" | "UNSUBSCRIBE") => vec![reply(request, json!({ "evt": request["evt"] }))],
+            _ => responder(request),
+        })
+    }
+
+    /// Queues a frame to be read by the client.
+    pub fn push(&mut self, opcode: u32, payload: Value) {
+        let data = payload.to_string();
+        self.incoming.extend(opcode.to_le_bytes());
+        self.incoming.extend((data.len() as u32).to_le_bytes());
+        self.incoming.extend(data.into_bytes());
+    }
+
+    /// The commands sent by the client, excluding the handshake.
+    pub fn commands(&self) -> Vec<&Value> {
+        self.sent.iter().filter(|(op, _)| *op == 1).map(|(_, v)| v).collect()
+    }
+}
+
+/// Builds a successful response to `request`.
+pub fn reply(request: &Value, data: Value) -> Value {
+    json!({ "cmd": request["cmd"], "nonce": request["nonce"], "evt": null, "data": data })
+}
+
+/// Builds an error response to `request`.
+pub fn error(request: &Value, code: u16, message: &str) -> Value {
+    json!({ "cmd": request["cmd"], "nonce": request["nonce"], "evt": "ERROR", "data": { "code": code, "message": message } })
+}
+
+/// Builds a close frame, closing the connection with a critical error.
+pub fn close(code: u16, message: &str) -> Value {
+    json!({ "__close": { "code": code, "message": message } })
+}
+
+/// Builds a dispatched event.
+pub fn event(evt: &str, data: Value) -> Value {
+    json!({ "cmd": "DISPATCH", "nonce": null, "evt": evt, "data": data })
+}
+
+/// A minimal user payload.
+pub fn user(id: &str, username: &str) -> Value {
+    json!({ "id": id, "username": username, "discriminator": "0", "avatar": null })
+}
+
+impl DiscordIpc for MockIpc {
+    fn connect_ipc(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<()> {
+        self.written.extend_from_slice(data);
+
+        while self.written.len() >= 8 {
+            let opcode = u32::from_le_bytes(self.written[0..4].try_into().unwrap());
+            let length = u32::from_le_bytes(self.written[4..8].try_into().unwrap()) as usize;
+            if self.written.len() < 8 + length {
+                break;
+            }
+
+            let frame: Vec<u8> = self.written.drain(..8 + length).collect();
+            let payload: Value = serde_json::from_slice(&frame[8..])?;
+            let responses = match opcode {
+                0 => vec![event("READY", json!({ "v": 1 }))],
+                _ => (self.responder)(&payload),
+            };
+            self.sent.push((opcode, payload));
+            for response in responses {
+                match response.get("__close") {
+                    Some(close) => self.push(2, close.clone()),
+                    None => self.push(1, response),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read(&mut self, buffer: &mut [u8]) -> Result<()> {
+        if self.incoming.len() < buffer.len() {
+            Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof))?;
+        }
+        for byte in buffer.iter_mut() {
+            *byte = self.incoming.pop_front().unwrap();
+        }
+
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.closed += 1;
+        Ok(())
+    }
+
+    fn get_client_id(&self) -> &Snowflake {
+        &self.client_id
+    }
+
+    fn get_state(&self) -> &ClientState {
+        &self.state
+    }
+
+    fn get_state_mut(&mut self) -> &mut ClientState {
+        &mut self.state
+    }
+}
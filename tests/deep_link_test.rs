@@ -0,0 +1,62 @@
+mod common;
+
+use common::{error, reply, MockIpc};
+use discord_ipc_rp::{
+    models::{DeepLink, Snowflake},
+    DiscordIPCErrorCode, DiscordIPCErrorCodeNonCritical, DiscordIpc, Error, Result,
+};
+use serde_json::{json, Value};
+
+#[test]
+fn test_deep_links() -> Result<()> {
+    let mut client = MockIpc::new(|request| vec![reply(request, Value::Null)]);
+    client.connect()?;
+
+    client.deep_link(&DeepLink::Channel {
+        guild_id: Some(Snowflake::new(199737254929760256)),
+        channel_id: Snowflake::new(199737254929760257),
+        message_id: None,
+    })?;
+    client.deep_link(&DeepLink::UserSettings { section: Some("voice".to_string()) })?;
+    client.deep_link(&DeepLink::StoreListing { sku_id: Snowflake::new(853169925467193344) })?;
+    client.deep_link(&DeepLink::Library)?;
+
+    let args: Vec<_> = client.commands().iter().map(|command| command["args"].clone()).collect();
+    assert_eq!(
+        args,
+        [
+            json!({ "type": "CHANNEL", "params": { "guildId": "199737254929760256", "channelId": "199737254929760257" } }),
+            json!({ "type": "USER_SETTINGS", "params": { "section": "voice" } }),
+            json!({ "type": "STORE_LISTING", "params": { "skuId": "853169925467193344" } }),
+            json!({ "type": "LIBRARY" }),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_browser_commands() -> Result<()> {
+    let mut client = MockIpc::new(|request| match request["args"]["code"].as_str() {
+        Some("expired") => vec![error(request, 4011, "Invalid invite")],
+        _ => vec![reply(request, Value::Null)],
+    });
+    client.connect()?;
+
+    client.invite_browser("discord-developers")?;
+    client.guild_template_browser("hgM48av5Q69A")?;
+    client.gift_code_browser("abcdefghijklmnop")?;
+    client.browser_handoff("handoff-token", "fingerprint")?;
+    assert!(matches!(
+        client.invite_browser("expired"),
+        Err(Error::Discord(error)) if error.code == DiscordIPCErrorCode::NonCritical(DiscordIPCErrorCodeNonCritical::InvalidInvite)
+    ));
+
+    let commands = client.commands();
+    assert_eq!((&commands[0]["cmd"], &commands[0]["args"]), (&json!("INVITE_BROWSER"), &json!({ "code": "discord-developers" })));
+    assert_eq!(commands[1]["cmd"], "GUILD_TEMPLATE_BROWSER");
+    assert_eq!(commands[2]["cmd"], "GIFT_CODE_BROWSER");
+    assert_eq!(commands[3]["args"], json!({ "handoffToken": "handoff-token", "fingerprint": "fingerprint" }));
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use common::{error, reply, MockIpc};
+use discord_ipc_rp::{
+    models::{ChannelType, Snowflake},
+    DiscordIPCErrorCode, DiscordIPCErrorCodeNonCritical, DiscordIpc, Error, Result,
+};
+use serde_json::json;
+
+fn client() -> MockIpc {
+    MockIpc::new(|request| match request["cmd"].as_str() {
+        Some("GET_GUILDS") => vec![reply(
+            request,
+            json!({ "guilds": [
+                { "id": "199737254929760256", "name": "test", "icon_url": null },
+                { "id": "199737254929760257", "name": "other", "icon_url": "https://cdn.discordapp.com/icons/a.jpg" }
+            ] }),
+        )],
+        Some("GET_GUILD") => vec![reply(
+            request,
+            json!({ "id": request["args"]["guild_id"], "name": "test", "icon_url": null, "members": [] }),
+        )],
+        Some("GET_CHANNELS") => vec![reply(
+            request,
+            json!({ "channels": [
+                { "id": "199737254929760256", "name": "general", "type": 0 },
+                { "id": "199737254929760257", "name": "General", "type": 2 },
+                { "id": "199737254929760258", "name": "future", "type": 99 }
+            ] }),
+        )],
+        Some("GET_CHANNEL") if request["args"]["channel_id"] == "1" => vec![error(request, 4005, "Invalid channel id: 1")],
+        Some("GET_CHANNEL") if request["args"]["channel_id"] == "2" => vec![error(request, 4999, "Something new")],
+        Some("GET_CHANNEL") => vec![reply(
+            request,
+            json!({
+                "id": request["args"]["channel_id"], "guild_id": "199737254929760256", "name": "General", "type": 2,
+                "bitrate": 64000, "user_limit": 0, "position": 0, "voice_states": [], "messages": []
+            }),
+        )],
+        Some("CREATE_CHANNEL_INVITE") => vec![reply(
+            request,
+            json!({ "code": "abcdef", "uses": 0, "max_uses": 0, "max_age": 86400, "temporary": false, "created_at": "2017-05-08T20:19:55.433000+00:00" }),
+        )],
+        _ => vec![],
+    })
+}
+
+#[test]
+fn test_guilds() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    let guilds = client.get_guilds()?;
+    assert_eq!(guilds.len(), 2);
+    assert_eq!(guilds[1].name, "other");
+    assert_eq!(guilds[1].icon_url.as_deref(), Some("https://cdn.discordapp.com/icons/a.jpg"));
+
+    let guild = client.get_guild(Snowflake::new(199737254929760256))?;
+    assert_eq!(guild.id, Snowflake::new(199737254929760256));
+    assert_eq!(client.commands()[1]["args"], json!({ "guild_id": "199737254929760256" }));
+
+    Ok(())
+}
+
+#[test]
+fn test_channels() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    let channels = client.get_channels(Snowflake::new(199737254929760256))?;
+    let kinds: Vec<_> = channels.iter().map(|channel| channel.kind).collect();
+    assert_eq!(kinds, [ChannelType::GuildText, ChannelType::GuildVoice, ChannelType::Unknown]);
+
+    let channel = client.get_channel(Snowflake::new(199737254929760257))?;
+    assert_eq!(channel.guild_id, Some(Snowflake::new(199737254929760256)));
+    assert_eq!(channel.bitrate, Some(64000));
+
+    match client.get_channel(Snowflake::new(1)) {
+        Err(Error::Discord(error)) => {
+            assert_eq!(error.code, DiscordIPCErrorCode::NonCritical(DiscordIPCErrorCodeNonCritical::InvalidChannel))
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    // unknown error codes are kept as they are
+    match client.get_channel(Snowflake::new(2)) {
+        Err(Error::UnknownDiscordError { code, message }) => assert_eq!((code, message.as_str()), (4999, "Something new")),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_create_channel_invite() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    let invite = client.create_channel_invite(Snowflake::new(199737254929760256))?;
+    assert_eq!(invite.code, "abcdef");
+    assert_eq!(invite.max_age, 86400);
+    assert_eq!(client.commands()[0]["args"], json!({ "channel_id": "199737254929760256" }));
+
+    Ok(())
+}
@@ -0,0 +1,179 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use common::{error, event, reply, user, MockIpc};
+use discord_ipc_rp::{
+    events::Event,
+    models::{
+        LobbyOptions, LobbySearchCast, LobbySearchComparison, LobbySearchDistance, LobbySearchQuery, LobbyType, Snowflake,
+    },
+    DiscordIpc, Error, Result,
+};
+use serde_json::{json, Value};
+
+fn lobby(request: &Value) -> Value {
+    json!({
+        "id": "1100000000000000001",
+        "type": request["args"]["type"],
+        "owner_id": "53908232506183680",
+        "secret": "lobby-secret",
+        "capacity": request["args"]["capacity"],
+        "locked": false,
+        "metadata": request["args"]["metadata"],
+        "members": [{ "user": user("53908232506183680", "mason"), "metadata": {} }],
+        "application_id": "771124766517755954",
+    })
+}
+
+#[test]
+fn test_lobby_lifecycle() -> Result<()> {
+    let mut client = MockIpc::new(|request| match request["cmd"].as_str() {
+        Some("CREATE_LOBBY") => vec![reply(request, lobby(request))],
+        _ => vec![reply(request, Value::Null)],
+    });
+    client.connect()?;
+
+    let options = LobbyOptions::new().kind(LobbyType::Public).capacity(4).metadata("mode", "ranked");
+    let created = client.create_lobby(&options)?;
+    assert_eq!(created.kind, LobbyType::Public);
+    assert_eq!(created.capacity, 4);
+    assert_eq!(created.metadata["mode"], "ranked");
+    assert_eq!(created.members[0].user.username, "mason");
+
+    let owner = Snowflake::new(82198898841029460);
+    client.update_lobby(created.id, &LobbyOptions::new().locked(true).owner(owner))?;
+    client.update_lobby_member(created.id, owner, &BTreeMap::from([("team".to_string(), "red".to_string())]))?;
+    client.delete_lobby(created.id)?;
+
+    let commands = client.commands();
+    assert_eq!(commands[0]["args"], json!({ "type": 2, "capacity": 4, "metadata": { "mode": "ranked" } }));
+    assert_eq!(commands[1]["cmd"], "UPDATE_LOBBY");
+    assert_eq!(commands[1]["args"], json!({ "id": "1100000000000000001", "owner_id": "82198898841029460", "locked": true }));
+    assert_eq!(commands[2]["cmd"], "UPDATE_LOBBY_MEMBER");
+    assert_eq!(
+        commands[2]["args"],
+        json!({ "lobby_id": "1100000000000000001", "user_id": "82198898841029460", "metadata": { "team": "red" } })
+    );
+    assert_eq!(commands[3]["cmd"], "DELETE_LOBBY");
+    assert_eq!(commands[3]["args"], json!({ "id": "1100000000000000001" }));
+
+    Ok(())
+}
+
+#[test]
+fn test_connect_to_lobby() -> Result<()> {
+    let mut client = MockIpc::new(|request| match request["cmd"].as_str() {
+        Some("CONNECT_TO_LOBBY") => {
+            let mut lobby = lobby(request);
+            lobby["type"] = json!(1);
+            lobby["capacity"] = json!(2);
+            lobby["metadata"] = json!({});
+            vec![reply(request, lobby)]
+        }
+        _ => vec![reply(request, Value::Null)],
+    });
+    client.connect()?;
+
+    let id = Snowflake::new(1100000000000000001);
+    let lobby = client.connect_to_lobby(id, "lobby-secret")?;
+    assert_eq!(lobby.kind, LobbyType::Private);
+    client.disconnect_from_lobby(id)?;
+
+    let commands = client.commands();
+    assert_eq!(commands[0]["args"], json!({ "id": "1100000000000000001", "secret": "lobby-secret" }));
+    assert_eq!(commands[1]["cmd"], "DISCONNECT_FROM_LOBBY");
+
+    Ok(())
+}
+
+#[test]
+fn test_lobby_errors() -> Result<()> {
+    let mut client = MockIpc::new(|request| match request["args"]["secret"].as_str() {
+        Some("wrong") => vec![error(request, 4014, "Invalid lobby secret")],
+        Some(_) => vec![error(request, 5007, "Lobby is full")],
+        None => vec![error(request, 4013, "Invalid lobby")],
+    });
+    client.connect()?;
+
+    let id = Snowflake::new(1100000000000000001);
+    assert!(matches!(client.connect_to_lobby(id, "wrong"), Err(Error::InvalidLobbySecret)));
+    assert!(matches!(client.connect_to_lobby(id, "lobby-secret"), Err(Error::LobbyFull)));
+    assert!(matches!(client.delete_lobby(id), Err(Error::InvalidLobby)));
+
+    Ok(())
+}
+
+#[test]
+fn test_search_lobbies() -> Result<()> {
+    let mut client = MockIpc::new(|request| match request["cmd"].as_str() {
+        Some("SEARCH_LOBBIES") => {
+            let mut lobby = lobby(request);
+            lobby["type"] = json!(2);
+            lobby["capacity"] = json!(8);
+            lobby["metadata"] = json!({ "mode": "ranked" });
+            vec![reply(request, json!([lobby]))]
+        }
+        _ => vec![reply(request, Value::Null)],
+    });
+    client.connect()?;
+
+    let query = LobbySearchQuery::new()
+        .filter("mode", LobbySearchComparison::Equal, "ranked", LobbySearchCast::String)
+        .filter("slots", LobbySearchComparison::GreaterThanOrEqual, "2", LobbySearchCast::Number)
+        .sort("rating", "1200", LobbySearchCast::Number)
+        .limit(10)
+        .distance(LobbySearchDistance::Global);
+    let lobbies = client.search_lobbies(&query)?;
+    assert_eq!(lobbies.len(), 1);
+    assert_eq!(lobbies[0].metadata["mode"], "ranked");
+
+    assert_eq!(
+        client.commands()[0]["args"],
+        json!({
+            "filter": [
+                { "key": "metadata.mode", "value": "ranked", "comparison": 0, "cast": 1 },
+                { "key": "metadata.slots", "value": "2", "comparison": 2, "cast": 2 },
+            ],
+            "sort": [{ "key": "metadata.rating", "value": "1200", "cast": 2 }],
+            "limit": 10,
+            "distance": 3,
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_lobby_messaging() -> Result<()> {
+    let mut client = MockIpc::with_subscriptions(|request| vec![reply(request, Value::Null)]);
+    client.connect()?;
+
+    let id = Snowflake::new(1100000000000000001);
+    client.subscribe_lobby_events(id)?;
+    client.connect_to_lobby_voice(id)?;
+    client.send_to_lobby(id, &[0, 159, 146, 150])?;
+    client.disconnect_from_lobby_voice(id)?;
+
+    let commands = client.commands();
+    let subscribed: Vec<_> = commands[..6].iter().map(|command| command["evt"].clone()).collect();
+    assert_eq!(
+        subscribed,
+        ["LOBBY_UPDATE", "LOBBY_DELETE", "LOBBY_MEMBER_CONNECT", "LOBBY_MEMBER_DISCONNECT", "LOBBY_MEMBER_UPDATE", "LOBBY_MESSAGE"]
+    );
+    assert_eq!(commands[0]["args"], json!({ "lobby_id": "1100000000000000001" }));
+    assert_eq!(commands[6]["cmd"], "CONNECT_TO_LOBBY_VOICE");
+    assert_eq!(commands[7]["args"], json!({ "lobby_id": "1100000000000000001", "data": "AJ+Slg==" }));
+    assert_eq!(commands[8]["cmd"], "DISCONNECT_FROM_LOBBY_VOICE");
+
+    let member = json!({ "lobby_id": "1100000000000000001", "member": { "user": user("82198898841029460", "ana"), "metadata": {} } });
+    client.push(1, event("LOBBY_MEMBER_CONNECT", member));
+    client.push(1, event("LOBBY_MESSAGE", json!({ "lobby_id": "1100000000000000001", "sender_id": "82198898841029460", "data": "aGk=" })));
+    client.push(1, event("LOBBY_DELETE", json!({ "id": "1100000000000000001", "reason": 0 })));
+
+    assert!(matches!(client.recv_event()?, Event::LobbyMemberConnect(connect) if connect.member.user.username == "ana"));
+    assert!(matches!(client.recv_event()?, Event::LobbyMessage(message) if message.data == b"hi"));
+    assert!(matches!(client.recv_event()?, Event::LobbyDelete(delete) if delete.id == id));
+
+    Ok(())
+}
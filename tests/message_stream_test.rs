@@ -0,0 +1,123 @@
+mod common;
+
+use common::{event, reply, user, MockIpc};
+use discord_ipc_rp::{events::Event, messages::MessageStream, models::Snowflake, DiscordIpc, Result};
+use serde_json::{json, Value};
+
+const GENERAL: &str = "199737254929760257";
+
+fn message(id: &str, content: &str) -> Value {
+    json!({
+        "id": id,
+        "author": user("53908232506183680", "mason"),
+        "content": content,
+        "timestamp": "2024-01-01T12:00:00.000000+00:00",
+        "edited_timestamp": null,
+        "embeds": [],
+        "attachments": [],
+    })
+}
+
+fn client() -> MockIpc {
+    MockIpc::with_subscriptions(|request| match request["cmd"].as_str() {
+        Some("GET_CHANNEL") => vec![reply(
+            request,
+            json!({ "id": GENERAL, "guild_id": null, "name": "general", "type": 0, "messages": [message("2", "second"), message("1", "first")] }),
+        )],
+        _ => vec![],
+    })
+}
+
+fn contents<C: DiscordIpc>(stream: &MessageStream<'_, C>) -> Vec<String> {
+    stream.history().map(|message| message.content.clone()).collect()
+}
+
+#[test]
+fn test_stream_tracks_history() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    let channel_id: Snowflake = GENERAL.parse()?;
+    let mut stream = MessageStream::subscribe(&mut client, channel_id, 3)?;
+    assert_eq!(contents(&stream), ["first", "second"]);
+
+    let mut edited = message("3", "thrid");
+    stream.client().push(1, event("MESSAGE_CREATE", json!({ "channel_id": GENERAL, "message": edited })));
+    edited["content"] = json!("third");
+    edited["edited_timestamp"] = json!("2024-01-01T12:01:00.000000+00:00");
+    stream.client().push(1, event("MESSAGE_UPDATE", json!({ "channel_id": GENERAL, "message": edited })));
+    stream.client().push(1, event("MESSAGE_CREATE", json!({ "channel_id": "1", "message": message("5", "elsewhere") })));
+    stream.client().push(1, event("MESSAGE_CREATE", json!({ "channel_id": GENERAL, "message": message("4", "fourth") })));
+    stream.client().push(1, event("MESSAGE_DELETE", json!({ "channel_id": GENERAL, "message": { "id": "3" } })));
+
+    assert!(matches!(stream.recv()?, Event::MessageCreate(_)));
+    assert_eq!(contents(&stream), ["first", "second", "thrid"]);
+    stream.recv()?;
+    assert_eq!(contents(&stream), ["first", "second", "third"]);
+    assert!(stream.message(Snowflake::new(3)).is_some_and(|message| message.edited_timestamp.is_some()));
+    stream.recv()?;
+    assert_eq!(contents(&stream), ["first", "second", "third"]);
+    stream.recv()?;
+    assert_eq!(contents(&stream), ["second", "third", "fourth"]);
+    stream.recv()?;
+    assert_eq!(contents(&stream), ["second", "fourth"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_stream_decodes_messages() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    let mut rich = message("3", "look");
+    rich["embeds"] = json!([{
+        "type": "rich", "title": "Patch notes", "color": 5814783,
+        "fields": [{ "name": "Version", "value": "1.2", "inline": true }],
+        "footer": { "text": "Released" },
+    }]);
+    rich["attachments"] = json!([{
+        "id": "10", "filename": "map.png", "size": 1024, "url": "https://cdn.discordapp.com/map.png",
+        "content_type": "image/png", "width": 64, "height": 64,
+    }]);
+    client.push(1, event("MESSAGE_CREATE", json!({ "channel_id": GENERAL, "message": rich })));
+
+    let Event::MessageCreate(created) = client.recv_event()? else { panic!("expected a message") };
+    let message = &created.message;
+    assert_eq!(message.author.as_ref().map(|author| author.username.as_str()), Some("mason"));
+    assert_eq!(message.timestamp.as_deref(), Some("2024-01-01T12:00:00.000000+00:00"));
+    assert_eq!(message.embeds[0].title.as_deref(), Some("Patch notes"));
+    assert!(message.embeds[0].fields[0].inline);
+    assert_eq!(message.attachments[0].content_type.as_deref(), Some("image/png"));
+
+    Ok(())
+}
+
+#[test]
+fn test_stream_unsubscribes_on_drop() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    drop(MessageStream::subscribe(&mut client, GENERAL.parse()?, 10)?);
+    let unsubscribed: Vec<_> = client
+        .commands()
+        .iter()
+        .filter(|command| command["cmd"] == "UNSUBSCRIBE")
+        .map(|command| (command["evt"].clone(), command["args"]["channel_id"].clone()))
+        .collect();
+    assert_eq!(
+        unsubscribed,
+        [
+            (json!("MESSAGE_CREATE"), json!(GENERAL)),
+            (json!("MESSAGE_UPDATE"), json!(GENERAL)),
+            (json!("MESSAGE_DELETE"), json!(GENERAL)),
+        ]
+    );
+
+    // the responses to the unsubscriptions were not waited for, and are skipped
+    let stream = MessageStream::subscribe(&mut client, GENERAL.parse()?, 10)?;
+    stream.close()?;
+    assert_eq!(client.commands().iter().filter(|command| command["cmd"] == "UNSUBSCRIBE").count(), 6);
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ use discord_ipc_rp::{Result, activity, DiscordIpc, DiscordIpcClient};
 
 #[test]
 fn test_models() -> Result<()> {
-    let mut client = DiscordIpcClient::new("771124766517755954");
+    let mut client = DiscordIpcClient::new("771124766517755954")?;
     client.connect()?;
 
     let activity = activity::Activity::new()
@@ -0,0 +1,56 @@
+mod common;
+
+use common::{event, user, MockIpc};
+use discord_ipc_rp::{models::Notification, notifications::NotificationBridge, DiscordIpc, Error, Result};
+use serde_json::{json, Value};
+
+fn client() -> MockIpc {
+    MockIpc::with_subscriptions(|_| vec![])
+}
+
+fn notification(title: &str, body: &str) -> Value {
+    json!({
+        "channel_id": "199737254929760257",
+        "message": { "id": "1", "author": user("53908232506183680", "mason"), "content": body },
+        "icon_url": "https://cdn.discordapp.com/avatars/53908232506183680/a.png",
+        "title": title,
+        "body": body,
+    })
+}
+
+#[test]
+fn test_bridge_forwards_notifications() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    let mut shown = Vec::new();
+    let mut bridge = NotificationBridge::subscribe(&mut client, |notification: &Notification| {
+        shown.push(format!("{}: {}", notification.title, notification.body));
+        Ok(())
+    })?;
+
+    client.push(1, event("NOTIFICATION_CREATE", notification("mason (#general)", "gg")));
+    client.push(1, event("VOICE_CHANNEL_SELECT", json!({ "channel_id": null })));
+    assert!(bridge.handle_event(&client.recv_event()?)?);
+    assert!(!bridge.handle_event(&client.recv_event()?)?);
+    let _sink = bridge.unsubscribe(&mut client)?;
+
+    assert_eq!(shown, ["mason (#general): gg"]);
+    let commands = client.commands();
+    assert_eq!((&commands[0]["cmd"], &commands[0]["evt"]), (&json!("SUBSCRIBE"), &json!("NOTIFICATION_CREATE")));
+    assert_eq!((&commands[1]["cmd"], &commands[1]["evt"]), (&json!("UNSUBSCRIBE"), &json!("NOTIFICATION_CREATE")));
+
+    Ok(())
+}
+
+#[test]
+fn test_sink_errors() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    let mut bridge = NotificationBridge::new(|_: &Notification| Err(Error::Notification("server unavailable".into())));
+    client.push(1, event("NOTIFICATION_CREATE", notification("mason", "gg")));
+    assert!(matches!(bridge.handle_event(&client.recv_event()?), Err(Error::Notification(_))));
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use common::{event, reply, MockIpc};
+use discord_ipc_rp::{
+    events::Event,
+    models::{ActivityActionType, OverlayState},
+    DiscordIpc, Result,
+};
+use serde_json::{json, Value};
+
+fn client() -> MockIpc {
+    MockIpc::with_subscriptions(|request| vec![reply(request, Value::Null)])
+}
+
+#[test]
+fn test_overlay_state() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    client.subscribe_overlay_events()?;
+    assert_eq!(client.overlay(), None);
+
+    client.push(1, event("OVERLAY", json!({ "enabled": true, "locked": false })));
+    assert!(matches!(client.recv_event()?, Event::Overlay(_)));
+    assert_eq!(client.overlay(), Some(OverlayState { enabled: true, locked: false }));
+
+    client.set_overlay_locked(true)?;
+    assert_eq!(client.overlay(), Some(OverlayState { enabled: true, locked: true }));
+
+    client.push(1, event("OVERLAY_UPDATE", json!({ "enabled": false, "locked": false })));
+    client.recv_event()?;
+    assert_eq!(client.overlay(), Some(OverlayState { enabled: false, locked: false }));
+
+    let pid = std::process::id();
+    let commands = client.commands();
+    assert_eq!((&commands[0]["evt"], &commands[0]["args"]), (&json!("OVERLAY"), &json!({ "pid": pid })));
+    assert_eq!(commands[1]["evt"], "OVERLAY_UPDATE");
+    assert_eq!(commands[2]["cmd"], "SET_OVERLAY_LOCKED");
+    assert_eq!(commands[2]["args"], json!({ "locked": true, "pid": pid }));
+
+    client.reconnect()?;
+    assert_eq!(client.overlay(), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_open_overlay_panels() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    client.open_overlay_activity_invite(ActivityActionType::Join)?;
+    client.open_overlay_guild_invite("discord-developers")?;
+    client.open_overlay_voice_settings()?;
+
+    let pid = std::process::id();
+    let commands = client.commands();
+    assert_eq!(commands[0]["cmd"], "OPEN_OVERLAY_ACTIVITY_INVITE");
+    assert_eq!(commands[0]["args"], json!({ "type": 1, "pid": pid }));
+    assert_eq!(commands[1]["cmd"], "OPEN_OVERLAY_GUILD_INVITE");
+    assert_eq!(commands[1]["args"], json!({ "code": "discord-developers", "pid": pid }));
+    assert_eq!(commands[2]["cmd"], "OPEN_OVERLAY_VOICE_SETTINGS");
+    assert_eq!(commands[2]["args"], json!({ "pid": pid }));
+
+    Ok(())
+}
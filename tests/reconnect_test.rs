@@ -2,7 +2,7 @@ use discord_ipc_rp::{Result, activity, DiscordIpc, DiscordIpcClient};
 
 #[test]
 fn test_reconnect() -> Result<()> {
-    let mut client = DiscordIpcClient::new("771124766517755954");
+    let mut client = DiscordIpcClient::new("771124766517755954")?;
     loop {
         if client.connect().is_ok() {
             break;
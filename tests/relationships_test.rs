@@ -0,0 +1,91 @@
+mod common;
+
+use common::{error, event, reply, user, MockIpc};
+use discord_ipc_rp::{
+    models::{RelationshipType, Snowflake, Status},
+    relationships::Relationships,
+    DiscordIpc, Error, Result,
+};
+use serde_json::{json, Value};
+
+const GAME: &str = "771124766517755954";
+
+fn relationship(kind: u8, id: &str, username: &str, status: &str, application_id: Option<&str>) -> Value {
+    let activity = application_id.map(|application_id| json!({ "name": "Game", "type": 0, "application_id": application_id }));
+
+    json!({ "type": kind, "user": user(id, username), "presence": { "status": status, "activity": activity } })
+}
+
+fn client() -> MockIpc {
+    MockIpc::with_subscriptions(|request| match request["cmd"].as_str() {
+        Some("GET_RELATIONSHIPS") => vec![reply(
+            request,
+            json!({ "relationships": [
+                relationship(1, "1", "alice", "online", Some(GAME)),
+                relationship(1, "2", "bob", "dnd", None),
+                relationship(2, "3", "carol", "offline", None),
+                relationship(3, "4", "dave", "idle", None),
+            ] }),
+        )],
+        Some("GET_USER") if request["args"]["id"] == "1" => vec![reply(request, user("1", "alice"))],
+        Some("GET_USER") => vec![error(request, 4010, "Invalid user")],
+        _ => vec![],
+    })
+}
+
+fn usernames<'a>(relationships: impl Iterator<Item = &'a discord_ipc_rp::models::Relationship>) -> Vec<&'a str> {
+    relationships.map(|relationship| relationship.user.username.as_str()).collect()
+}
+
+#[test]
+fn test_relationships_cache() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    let game: Snowflake = GAME.parse()?;
+    let mut relationships = Relationships::subscribe(&mut client)?;
+    assert_eq!(usernames(relationships.friends()), ["alice", "bob"]);
+    assert_eq!(usernames(relationships.blocked()), ["carol"]);
+    assert_eq!(usernames(relationships.incoming_requests()), ["dave"]);
+    assert_eq!(usernames(relationships.playing(game)), ["alice"]);
+    assert_eq!(relationships.presence(Snowflake::new(2)).map(|presence| presence.status), Some(Status::DoNotDisturb));
+
+    let updates = [
+        relationship(1, "2", "bob", "online", Some(GAME)),
+        relationship(1, "4", "dave", "idle", None),
+        relationship(0, "3", "carol", "offline", None),
+        relationship(4, "5", "erin", "offline", None),
+        relationship(1, "2", "bob", "online", Some(GAME)),
+    ];
+    let mut changed = Vec::new();
+    for update in updates {
+        client.push(1, event("RELATIONSHIP_UPDATE", update));
+        changed.push(relationships.handle_event(&client.recv_event()?));
+    }
+
+    assert_eq!(changed, [true, true, true, true, false]);
+    assert_eq!(usernames(relationships.friends()), ["alice", "bob", "dave"]);
+    assert_eq!(usernames(relationships.playing(game)), ["alice", "bob"]);
+    assert_eq!(relationships.blocked().count(), 0);
+    assert_eq!(relationships.get(Snowflake::new(5)).map(|r| r.kind), Some(RelationshipType::PendingOutgoing));
+
+    relationships.unsubscribe(&mut client)?;
+    let commands = client.commands();
+    assert_eq!((&commands[0]["cmd"], &commands[0]["evt"]), (&json!("SUBSCRIBE"), &json!("RELATIONSHIP_UPDATE")));
+    assert_eq!(commands[1]["cmd"], "GET_RELATIONSHIPS");
+    assert_eq!(commands[2]["cmd"], "UNSUBSCRIBE");
+
+    Ok(())
+}
+
+#[test]
+fn test_get_user() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    assert_eq!(client.get_user(Snowflake::new(1))?.username, "alice");
+    assert_eq!(client.commands()[0]["args"], json!({ "id": "1" }));
+    assert!(matches!(client.get_user(Snowflake::new(9)), Err(Error::Discord(_))));
+
+    Ok(())
+}
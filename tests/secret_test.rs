@@ -0,0 +1,92 @@
+mod common;
+
+use std::time::Duration;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use common::{event, MockIpc};
+use discord_ipc_rp::{
+    activity::{Activity, Secrets},
+    events::Event,
+    secret::{SecretKind, SecretPayload, SecretSigner, MAX_SECRET_LENGTH},
+    DiscordIpc, Error, Result,
+};
+use serde_json::json;
+
+const KEY: &[u8] = b"0123456789abcdef0123456789abcdef";
+
+#[test]
+fn test_sign_and_verify() -> Result<()> {
+    let signer = SecretSigner::new(KEY);
+    let payload = SecretPayload::new("party-1", Duration::from_secs(3600)).server("203.0.113.7:27015");
+
+    let join = signer.sign(SecretKind::Join, &payload)?;
+    let spectate = signer.sign(SecretKind::Spectate, &payload)?;
+    assert!(join.len() <= MAX_SECRET_LENGTH);
+    assert_ne!(join, spectate);
+
+    // the secrets can be set on an activity
+    let _ = Activity::new().secrets(Secrets::new().join(&join).spectate(&spectate));
+
+    let mut client = MockIpc::new(|_| vec![]);
+    client.connect()?;
+    client.push(1, event("ACTIVITY_JOIN", json!({ "secret": join })));
+    let Event::ActivityJoin(activity_join) = client.recv_event()? else { panic!("expected a join") };
+
+    let verified = signer.verify(SecretKind::Join, &activity_join.secret)?;
+    assert_eq!(verified.party_id, "party-1");
+    assert_eq!(verified.server.as_deref(), Some("203.0.113.7:27015"));
+
+    Ok(())
+}
+
+#[test]
+fn test_reject_forged_secrets() -> Result<()> {
+    let signer = SecretSigner::new(KEY);
+    let payload = SecretPayload::new("party-1", Duration::from_secs(3600));
+    let join = signer.sign(SecretKind::Join, &payload)?;
+
+    // signed with another key
+    assert!(matches!(SecretSigner::new(b"other key").verify(SecretKind::Join, &join), Err(Error::InvalidSecret)));
+    // used as another kind of secret
+    assert!(matches!(signer.verify(SecretKind::Spectate, &join), Err(Error::InvalidSecret)));
+    // tampered payload
+    let (_, tag) = join.split_once('.').unwrap();
+    let forged = URL_SAFE_NO_PAD.encode(br#"{"p":"party-2","e":99999999999}"#);
+    assert!(matches!(signer.verify(SecretKind::Join, &format!("{}.{}", forged, tag)), Err(Error::InvalidSecret)));
+    // malformed
+    for secret in ["", "abc", "abc.", ".abc", "abc.def"] {
+        assert!(matches!(signer.verify(SecretKind::Join, secret), Err(Error::InvalidSecret)));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_reject_expired_secrets() -> Result<()> {
+    let signer = SecretSigner::new(KEY);
+    let secret = signer.sign(SecretKind::Match, &SecretPayload::new("party-1", Duration::ZERO))?;
+
+    assert!(matches!(signer.verify(SecretKind::Match, &secret), Err(Error::SecretExpired)));
+
+    Ok(())
+}
+
+#[test]
+fn test_never_expiring_secrets() -> Result<()> {
+    let signer = SecretSigner::new(KEY);
+    let payload = SecretPayload::new("party-1", Duration::MAX);
+    assert_eq!(payload.expires_at, u64::MAX);
+
+    let secret = signer.sign(SecretKind::Join, &payload)?;
+    assert_eq!(signer.verify(SecretKind::Join, &secret)?.party_id, "party-1");
+
+    Ok(())
+}
+
+#[test]
+fn test_reject_long_secrets() {
+    let signer = SecretSigner::new(KEY);
+    let payload = SecretPayload::new(&"a".repeat(100), Duration::from_secs(3600));
+
+    assert!(matches!(signer.sign(SecretKind::Join, &payload), Err(Error::SecretTooLong(_))));
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{error, reply, MockIpc};
+use discord_ipc_rp::{models::Snowflake, DiscordIpc, Error, Result};
+use serde_json::{json, Value};
+
+fn channel(id: &Value) -> Value {
+    json!({ "id": id, "guild_id": "199737254929760256", "name": "Team", "type": 2, "voice_states": [] })
+}
+
+fn client() -> MockIpc {
+    MockIpc::new(|request| {
+        let args = &request["args"];
+        match request["cmd"].as_str() {
+            Some("SELECT_VOICE_CHANNEL") if args["channel_id"].is_null() => vec![reply(request, Value::Null)],
+            Some("SELECT_VOICE_CHANNEL") if args["channel_id"] == "1" => vec![error(request, 5001, "Request to select channel timed out")],
+            Some("SELECT_VOICE_CHANNEL") if args["force"] != true => vec![error(request, 5003, "User is already joined to a voice channel")],
+            Some("SELECT_VOICE_CHANNEL" | "SELECT_TEXT_CHANNEL") => vec![reply(request, channel(&args["channel_id"]))],
+            Some("GET_SELECTED_VOICE_CHANNEL") => vec![reply(request, Value::Null)],
+            _ => vec![],
+        }
+    })
+}
+
+#[test]
+fn test_select_voice_channel() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    let channel = client.select_voice_channel(Snowflake::new(199737254929760257), true, Some(Duration::from_secs(5)))?;
+    assert_eq!(channel.id, Snowflake::new(199737254929760257));
+    assert_eq!(
+        client.commands()[0]["args"],
+        json!({ "channel_id": "199737254929760257", "timeout": 5, "force": true })
+    );
+
+    client.leave_voice_channel()?;
+    assert_eq!(client.commands()[1]["args"], json!({ "channel_id": null }));
+
+    assert_eq!(client.get_selected_voice_channel()?, None);
+
+    // sub-second timeouts are rounded up to whole seconds
+    client.select_voice_channel(Snowflake::new(199737254929760257), true, Some(Duration::from_millis(500)))?;
+    assert_eq!(client.commands()[3]["args"]["timeout"], 1);
+    client.select_text_channel(Snowflake::new(199737254929760256), Some(Duration::from_millis(2500)))?;
+    assert_eq!(client.commands()[4]["args"]["timeout"], 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_select_voice_channel_errors() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    assert!(matches!(
+        client.select_voice_channel(Snowflake::new(199737254929760257), false, None),
+        Err(Error::SelectVoiceForceRequired)
+    ));
+    assert!(matches!(
+        client.select_voice_channel(Snowflake::new(1), true, None),
+        Err(Error::SelectChannelTimedOut)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_select_text_channel() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    let channel = client.select_text_channel(Snowflake::new(199737254929760258), None)?;
+    assert_eq!(channel.id, Snowflake::new(199737254929760258));
+    assert_eq!(client.commands()[0]["args"], json!({ "channel_id": "199737254929760258" }));
+
+    Ok(())
+}
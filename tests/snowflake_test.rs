@@ -0,0 +1,37 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use discord_ipc_rp::{models::Snowflake, DiscordIpcClient, Error};
+
+#[test]
+fn test_snowflake_parts() {
+    let id: Snowflake = "175928847299117063".parse().unwrap();
+
+    assert_eq!(id.get(), 175928847299117063);
+    assert_eq!(id.timestamp(), 1462015105796);
+    assert_eq!(id.created_at(), UNIX_EPOCH + Duration::from_millis(1462015105796));
+    assert_eq!(id.worker_id(), 1);
+    assert_eq!(id.process_id(), 0);
+    assert_eq!(id.sequence(), 7);
+}
+
+#[test]
+fn test_snowflake_serde() {
+    let id = Snowflake::new(771124766517755954);
+
+    assert_eq!(serde_json::to_string(&id).unwrap(), r#""771124766517755954""#);
+    assert_eq!(serde_json::from_str::<Snowflake>(r#""771124766517755954""#).unwrap(), id);
+    assert_eq!(serde_json::from_str::<Snowflake>("771124766517755954").unwrap(), id);
+    assert!(serde_json::from_str::<Snowflake>(r#""not a snowflake""#).is_err());
+    assert!(serde_json::from_str::<Snowflake>("-1").is_err());
+    assert!(serde_json::from_str::<Snowflake>("0").is_err());
+    assert!(serde_json::from_str::<Snowflake>(r#""0""#).is_err());
+}
+
+#[test]
+fn test_client_id_validation() {
+    assert!(DiscordIpcClient::new("771124766517755954").is_ok());
+
+    for invalid in ["", "0", "abc", "-771124766517755954", "+771124766517755954", "<some client id>"] {
+        assert!(matches!(DiscordIpcClient::new(invalid), Err(Error::InvalidSnowflake(_))));
+    }
+}
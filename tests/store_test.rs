@@ -0,0 +1,85 @@
+mod common;
+
+use common::{error, event, reply, MockIpc};
+use discord_ipc_rp::{
+    events::Event,
+    models::{EntitlementType, Snowflake, SkuType},
+    DiscordIpc, Error, Result,
+};
+use serde_json::{json, Value};
+
+const DLC: &str = "853169925467193344";
+
+fn entitlement(id: &str, sku_id: &str) -> Value {
+    json!({ "id": id, "type": 1, "sku_id": sku_id, "application_id": "771124766517755954", "user_id": "53908232506183680" })
+}
+
+fn client() -> MockIpc {
+    MockIpc::with_subscriptions(|request| match request["cmd"].as_str() {
+        Some("GET_SKUS") => vec![reply(
+            request,
+            json!([
+                { "id": "771124766517755954", "type": 1, "name": "Game", "price": { "amount": 1999, "currency": "usd" } },
+                { "id": DLC, "type": 2, "name": "Expansion", "price": null },
+            ]),
+        )],
+        Some("GET_ENTITLEMENTS") => vec![reply(request, json!([entitlement("1", DLC)]))],
+        Some("START_PURCHASE") if request["args"]["sku_id"] == DLC => vec![reply(request, Value::Null)],
+        Some("START_PURCHASE") => vec![error(request, 5008, "Purchase canceled")],
+        _ => vec![],
+    })
+}
+
+#[test]
+fn test_skus_and_entitlements() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    let skus = client.get_skus()?;
+    assert_eq!(skus[0].price.as_ref().map(|price| price.amount), Some(1999));
+    assert_eq!(skus[1].kind, SkuType::Dlc);
+    assert!(skus[1].price.is_none());
+
+    let entitlements = client.get_entitlements()?;
+    assert_eq!(entitlements[0].kind, EntitlementType::Purchase);
+    assert!(client.has_entitlement(DLC.parse()?)?);
+    assert!(!client.has_entitlement(Snowflake::new(1))?);
+
+    Ok(())
+}
+
+#[test]
+fn test_purchase() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    client.subscribe_entitlement_events()?;
+    client.start_purchase(DLC.parse()?)?;
+    assert!(matches!(client.start_purchase(Snowflake::new(1)), Err(Error::PurchaseCanceled)));
+
+    let commands = client.commands();
+    assert_eq!((&commands[0]["evt"], &commands[1]["evt"]), (&json!("ENTITLEMENT_CREATE"), &json!("ENTITLEMENT_DELETE")));
+    assert_eq!(commands[2]["args"]["sku_id"], DLC);
+    assert_eq!(commands[2]["args"]["pid"], std::process::id());
+
+    client.push(1, event("ENTITLEMENT_CREATE", entitlement("2", DLC)));
+    client.push(1, event("ENTITLEMENT_DELETE", entitlement("2", DLC)));
+    assert!(matches!(client.recv_event()?, Event::EntitlementCreate(entitlement) if entitlement.sku_id == DLC.parse()?));
+    assert!(matches!(client.recv_event()?, Event::EntitlementDelete(_)));
+
+    Ok(())
+}
+
+#[test]
+fn test_store_errors() -> Result<()> {
+    let mut client = MockIpc::new(|request| match request["cmd"].as_str() {
+        Some("START_PURCHASE") => vec![error(request, 5009, "Purchase error")],
+        _ => vec![error(request, 4015, "Invalid entitlement")],
+    });
+    client.connect()?;
+
+    assert!(matches!(client.start_purchase(Snowflake::new(1)), Err(Error::PurchaseFailed)));
+    assert!(matches!(client.get_entitlements(), Err(Error::InvalidEntitlement)));
+
+    Ok(())
+}
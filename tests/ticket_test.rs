@@ -0,0 +1,62 @@
+mod common;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use common::{error, reply, user, MockIpc};
+use discord_ipc_rp::{models::SignedTicket, DiscordIpc, Error, Result};
+use serde_json::{json, Value};
+
+fn ticket() -> String {
+    let claims = json!({
+        "application_id": "771124766517755954",
+        "user_id": "53908232506183680",
+        "user": user("53908232506183680", "mason"),
+        "entitlements": [{ "id": "1", "type": 1, "sku_id": "853169925467193344" }],
+        "expires": 1704110400,
+    });
+
+    format!("2.c2lnbmF0dXJl.{}", URL_SAFE_NO_PAD.encode(claims.to_string()))
+}
+
+#[test]
+fn test_tickets() -> Result<()> {
+    let mut client = MockIpc::new(|request| match request["cmd"].as_str() {
+        Some("GET_APPLICATION_TICKET") => vec![reply(request, json!(ticket()))],
+        Some("GET_ENTITLEMENT_TICKET") => vec![reply(request, json!({ "ticket": ticket() }))],
+        _ => vec![reply(request, Value::Null)],
+    });
+    client.connect()?;
+
+    client.validate_application()?;
+    let application = client.get_application_ticket()?;
+    assert_eq!(application.ticket, ticket());
+    assert_eq!((application.version.as_str(), application.signature.as_str()), ("2", "c2lnbmF0dXJl"));
+    assert_eq!(application.claims.user_id, Some("53908232506183680".parse()?));
+    assert_eq!(application.claims.user.as_ref().map(|user| user.username.as_str()), Some("mason"));
+    assert_eq!(application.claims.extra["expires"], 1704110400);
+
+    let entitlement = client.get_entitlement_ticket()?;
+    assert_eq!(entitlement.claims.entitlements[0].sku_id, "853169925467193344".parse()?);
+
+    let commands = client.commands();
+    assert_eq!(commands[0]["cmd"], "VALIDATE_APPLICATION");
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_tickets() -> Result<()> {
+    assert!(matches!("2.signature".parse::<SignedTicket>(), Err(Error::InvalidTicket)));
+    assert!(matches!("2.signature.not base64!".parse::<SignedTicket>(), Err(Error::InvalidTicket)));
+    assert!(matches!("2.signature.bm90IGpzb24".parse::<SignedTicket>(), Err(Error::InvalidTicket)));
+
+    let mut client = MockIpc::new(|request| match request["cmd"].as_str() {
+        Some("VALIDATE_APPLICATION") => vec![error(request, 4015, "Invalid entitlement")],
+        _ => vec![reply(request, json!("malformed"))],
+    });
+    client.connect()?;
+
+    assert!(matches!(client.validate_application(), Err(Error::InvalidEntitlement)));
+    assert!(matches!(client.get_application_ticket(), Err(Error::InvalidTicket)));
+
+    Ok(())
+}
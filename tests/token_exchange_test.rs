@@ -0,0 +1,194 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "http")]
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+};
+
+use common::{close, error, reply, user, MockIpc};
+use discord_ipc_rp::{
+    models::{Scope, Token},
+    oauth2::TokenExchanger,
+    ActivityEvent, DiscordIpc, Error, Result,
+};
+use serde_json::json;
+
+fn token(access_token: &str, refresh_token: &str) -> Token {
+    Token {
+        access_token: access_token.to_string(),
+        token_type: "Bearer".to_string(),
+        expires_in: 604800,
+        refresh_token: Some(refresh_token.to_string()),
+        scope: "rpc identify".to_string(),
+    }
+}
+
+fn authenticate_response(access_token: &str) -> serde_json::Value {
+    json!({
+        "access_token": access_token,
+        "user": user("53908232506183680", "mason"),
+        "scopes": ["rpc", "identify"],
+        "expires": "2017-05-08T20:19:55.433000+00:00",
+        "application": { "id": "771124766517755954", "name": "Test" }
+    })
+}
+
+/// A token exchanger that hands out numbered tokens.
+#[derive(Default)]
+struct CountingExchanger {
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+impl TokenExchanger for CountingExchanger {
+    fn exchange_code(&self, code: &str) -> Result<Token> {
+        self.calls.lock().unwrap().push(format!("code:{}", code));
+        Ok(token("token-1", "refresh-1"))
+    }
+
+    fn refresh_token(&self, refresh_token: &str) -> Result<Token> {
+        self.calls.lock().unwrap().push(format!("refresh:{}", refresh_token));
+        Ok(token("token-2", "refresh-2"))
+    }
+}
+
+/// Serves a single HTTP request, returning the request body.
+#[cfg(feature = "http")]
+fn serve_once(listener: TcpListener, response: &'static str) -> std::thread::JoinHandle<String> {
+    std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                length = value.trim().parse().unwrap();
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+
+        write!(
+            reader.get_mut(),
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            response.len(),
+            response
+        )
+        .unwrap();
+
+        format!("{}{}", request_line.trim(), String::from_utf8(body).unwrap())
+    })
+}
+
+#[test]
+#[cfg(feature = "http")]
+fn test_http_exchange_code() -> Result<()> {
+    use discord_ipc_rp::oauth2::HttpTokenExchanger;
+
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let base_url = format!("http://{}/api/", listener.local_addr()?);
+    let server = serve_once(
+        listener,
+        r#"{"access_token":"abc","token_type":"Bearer","expires_in":604800,"refresh_token":"def","scope":"rpc identify"}"#,
+    );
+
+    let exchanger = HttpTokenExchanger::new(discord_ipc_rp::models::Snowflake::new(771124766517755954))
+        .base_url(&base_url)
+        .client_secret("secret");
+    let token = exchanger.exchange_code("the-code")?;
+
+    assert_eq!(token.access_token, "abc");
+    assert_eq!(token.refresh_token.as_deref(), Some("def"));
+    assert_eq!(
+        server.join().unwrap(),
+        "POST /api/oauth2/token HTTP/1.1client_id=771124766517755954&grant_type=authorization_code&code=the-code&client_secret=secret"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_login_without_exchanger() -> Result<()> {
+    let mut client = MockIpc::new(|_| vec![]);
+    client.connect()?;
+
+    assert!(matches!(client.login(&[Scope::Rpc]), Err(Error::MissingTokenExchanger)));
+    assert!(client.commands().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_refresh_on_invalid_token() -> Result<()> {
+    let mut client = MockIpc::new(|request| match (request["cmd"].as_str(), request["args"]["access_token"].as_str()) {
+        (Some("AUTHORIZE"), _) => vec![reply(request, json!({ "code": "the-code" }))],
+        (Some("AUTHENTICATE"), Some("token-2")) => vec![reply(request, authenticate_response("token-2"))],
+        (Some("AUTHENTICATE"), _) => vec![error(request, 4009, "Invalid token")],
+        _ => vec![],
+    });
+    let exchanger = CountingExchanger::default();
+    let calls = exchanger.calls.clone();
+    client.set_token_exchanger(exchanger);
+    client.connect()?;
+
+    let response = client.login(&[Scope::Rpc, Scope::Identify])?;
+    assert_eq!(response.access_token, "token-2");
+    assert_eq!(*calls.lock().unwrap(), ["code:the-code", "refresh:refresh-1"]);
+    assert_eq!(client.scopes(), &[Scope::Rpc, Scope::Identify]);
+
+    Ok(())
+}
+
+#[test]
+fn test_refresh_on_revoked_token() -> Result<()> {
+    let revoked = Arc::new(Mutex::new(false));
+    let mut client = MockIpc::with_subscriptions({
+        let revoked = revoked.clone();
+        move |request| match request["cmd"].as_str() {
+            Some("AUTHORIZE") => vec![reply(request, json!({ "code": "the-code" }))],
+            Some("AUTHENTICATE") => vec![reply(request, authenticate_response(request["args"]["access_token"].as_str().unwrap()))],
+            Some("GET_GUILDS") if !*revoked.lock().unwrap() => {
+                *revoked.lock().unwrap() = true;
+                vec![close(4003, "Token revoked")]
+            }
+            Some("GET_GUILDS") => vec![reply(request, json!({ "guilds": [] }))],
+            _ => vec![],
+        }
+    });
+    client.set_token_exchanger(CountingExchanger::default());
+    client.connect()?;
+    client.login(&[Scope::Rpc])?;
+    client.subscribe(ActivityEvent::ActivityJoin, json!({}))?;
+
+    let guilds: serde_json::Value = client.send_command(discord_ipc_rp::ActivityCmd::GetGuilds, json!({}))?;
+    assert_eq!(guilds, json!({ "guilds": [] }));
+
+    let authenticated: Vec<_> = client
+        .commands()
+        .iter()
+        .filter(|command| command["cmd"] == "AUTHENTICATE")
+        .map(|command| command["args"]["access_token"].clone())
+        .collect();
+    assert_eq!(authenticated, [json!("token-1"), json!("token-2")]);
+    // the connection was closed and re-established before re-authenticating
+    assert_eq!(client.closed, 1);
+    assert_eq!(client.sent.iter().filter(|(op, _)| *op == 0).count(), 2);
+
+    // and the subscriptions of the closed session were restored
+    let commands: Vec<_> = client.commands().iter().map(|command| command["cmd"].clone()).collect();
+    assert_eq!(
+        commands,
+        [json!("AUTHORIZE"), json!("AUTHENTICATE"), json!("SUBSCRIBE"), json!("GET_GUILDS"), json!("AUTHENTICATE"), json!("SUBSCRIBE"), json!("GET_GUILDS")]
+    );
+    assert_eq!(client.commands()[5]["evt"], "ACTIVITY_JOIN");
+
+    Ok(())
+}
@@ -0,0 +1,188 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use common::{error, reply, user, MockIpc};
+use discord_ipc_rp::{
+    models::{Scope, Snowflake, StoredToken, Token},
+    oauth2::{FileTokenStore, TokenExchanger, TokenStore},
+    DiscordIpc, Result,
+};
+use serde_json::json;
+
+const CLIENT_ID: Snowflake = Snowflake::new(771124766517755954);
+
+fn token(access_token: &str, expires_in: u64) -> Token {
+    Token {
+        access_token: access_token.to_string(),
+        token_type: "Bearer".to_string(),
+        expires_in,
+        refresh_token: Some(format!("refresh-{}", access_token)),
+        scope: "rpc".to_string(),
+    }
+}
+
+/// A token store shared with the test, so that it can be inspected.
+#[derive(Clone, Default)]
+struct MemoryTokenStore(Arc<Mutex<Option<StoredToken>>>);
+
+impl TokenStore for MemoryTokenStore {
+    fn load(&self, _: Snowflake) -> Result<Option<StoredToken>> {
+        Ok(self.0.lock().unwrap().clone())
+    }
+
+    fn save(&self, _: Snowflake, token: &StoredToken) -> Result<()> {
+        *self.0.lock().unwrap() = Some(token.clone());
+        Ok(())
+    }
+
+    fn clear(&self, _: Snowflake) -> Result<()> {
+        *self.0.lock().unwrap() = None;
+        Ok(())
+    }
+}
+
+struct FixedExchanger;
+
+impl TokenExchanger for FixedExchanger {
+    fn exchange_code(&self, _: &str) -> Result<Token> {
+        Ok(token("authorized", 3600))
+    }
+
+    fn refresh_token(&self, _: &str) -> Result<Token> {
+        Ok(token("refreshed", 3600))
+    }
+}
+
+/// A client that accepts the `valid`, `authorized` and `refreshed` tokens.
+fn client() -> MockIpc {
+    MockIpc::new(|request| match request["cmd"].as_str() {
+        Some("AUTHORIZE") => vec![reply(request, json!({ "code": "the-code" }))],
+        Some("AUTHENTICATE") => match request["args"]["access_token"].as_str() {
+            Some(access_token @ ("valid" | "authorized" | "refreshed")) => vec![reply(
+                request,
+                json!({
+                    "access_token": access_token,
+                    "user": user("53908232506183680", "mason"),
+                    "scopes": ["rpc"],
+                    "expires": "2017-05-08T20:19:55.433000+00:00",
+                    "application": { "id": "771124766517755954", "name": "Test" }
+                }),
+            )],
+            _ => vec![error(request, 4009, "Invalid token")],
+        },
+        _ => vec![],
+    })
+}
+
+fn authenticated_with(client: &MockIpc) -> Vec<String> {
+    client
+        .commands()
+        .iter()
+        .filter(|command| command["cmd"] == "AUTHENTICATE" || command["cmd"] == "AUTHORIZE")
+        .map(|command| command["args"]["access_token"].as_str().unwrap_or("<authorize>").to_string())
+        .collect()
+}
+
+#[test]
+fn test_connect_with_stored_token() -> Result<()> {
+    let store = MemoryTokenStore::default();
+    store.save(CLIENT_ID, &StoredToken::new(token("valid", 3600)))?;
+
+    let mut client = client();
+    client.set_token_exchanger(FixedExchanger);
+    client.set_token_store(store.clone(), &[Scope::Rpc]);
+    client.connect()?;
+
+    assert_eq!(authenticated_with(&client), ["valid"]);
+    assert_eq!(client.scopes(), &[Scope::Rpc]);
+
+    Ok(())
+}
+
+#[test]
+fn test_connect_refreshes_expired_token() -> Result<()> {
+    let store = MemoryTokenStore::default();
+    store.save(CLIENT_ID, &StoredToken::new(token("valid", 0)))?;
+
+    let mut client = client();
+    client.set_token_exchanger(FixedExchanger);
+    client.set_token_store(store.clone(), &[Scope::Rpc]);
+    client.connect()?;
+
+    assert_eq!(authenticated_with(&client), ["refreshed"]);
+    assert_eq!(store.0.lock().unwrap().as_ref().unwrap().token.access_token, "refreshed");
+
+    Ok(())
+}
+
+#[test]
+fn test_connect_falls_back_to_authorize() -> Result<()> {
+    let store = MemoryTokenStore::default();
+    let mut client = client();
+    client.set_token_exchanger(FixedExchanger);
+    client.set_token_store(store.clone(), &[Scope::Rpc]);
+    client.connect()?;
+
+    assert_eq!(authenticated_with(&client), ["<authorize>", "authorized"]);
+    let stored = store.0.lock().unwrap().clone().unwrap();
+    assert_eq!(stored.token.access_token, "authorized");
+    assert!(!stored.is_expired());
+
+    Ok(())
+}
+
+#[test]
+fn test_stored_token_with_huge_lifetime() {
+    let stored = StoredToken::new(token("valid", u64::MAX));
+    assert_eq!(stored.expires_at, u64::MAX);
+    assert!(!stored.is_expired());
+}
+
+#[test]
+fn test_file_token_store() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("discord-ipc-rp-{}", uuid::Uuid::new_v4()));
+    let store = FileTokenStore::with_dir(&dir);
+    let stored = StoredToken::new(token("valid", 3600));
+
+    assert_eq!(store.load(CLIENT_ID)?, None);
+    store.save(CLIENT_ID, &stored)?;
+    assert_eq!(store.load(CLIENT_ID)?, Some(stored));
+    assert_eq!(store.path(CLIENT_ID), dir.join("771124766517755954.json"));
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(store.path(CLIENT_ID))?.permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    store.clear(CLIENT_ID)?;
+    assert_eq!(store.load(CLIENT_ID)?, None);
+    store.clear(CLIENT_ID)?;
+
+    std::fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_connect_discards_corrupt_token_file() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("discord-ipc-rp-{}", uuid::Uuid::new_v4()));
+    let store = FileTokenStore::with_dir(&dir);
+    store.save(CLIENT_ID, &StoredToken::new(token("valid", 3600)))?;
+    std::fs::write(store.path(CLIENT_ID), r#"{"access_token":"val"#)?;
+    assert!(store.load(CLIENT_ID).is_err());
+
+    let mut client = client();
+    client.set_token_exchanger(FixedExchanger);
+    client.set_token_store(FileTokenStore::with_dir(&dir), &[Scope::Rpc]);
+    client.connect()?;
+
+    assert_eq!(authenticated_with(&client), ["<authorize>", "authorized"]);
+    assert_eq!(store.load(CLIENT_ID)?.map(|stored| stored.token.access_token), Some("authorized".to_string()));
+    // only the token file is left, without temporary files
+    assert_eq!(std::fs::read_dir(&dir)?.count(), 1);
+
+    std::fs::remove_dir_all(dir)?;
+    Ok(())
+}
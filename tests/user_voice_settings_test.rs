@@ -0,0 +1,63 @@
+mod common;
+
+use common::{reply, user, MockIpc};
+use discord_ipc_rp::{
+    models::{Pan, Snowflake, UserVoiceSettings},
+    DiscordIpc, Result,
+};
+use serde_json::json;
+
+#[test]
+fn test_set_user_voice_settings() -> Result<()> {
+    let mut client = MockIpc::new(|request| match request["cmd"].as_str() {
+        Some("SET_USER_VOICE_SETTINGS") => vec![reply(
+            request,
+            json!({ "user_id": request["args"]["user_id"], "pan": { "left": 1.0, "right": 0.5 }, "volume": 150.0, "mute": false }),
+        )],
+        _ => vec![],
+    });
+    client.connect()?;
+
+    let settings = UserVoiceSettings::new(Snowflake::new(53908232506183680))
+        .volume(150.0)
+        .pan(Pan { left: 1.0, right: 0.5 });
+    let updated = client.set_user_voice_settings(&settings)?;
+
+    assert_eq!(updated.mute, Some(false));
+    assert_eq!(updated.volume, Some(150.0));
+    assert_eq!(
+        client.commands()[0]["args"],
+        json!({ "user_id": "53908232506183680", "pan": { "left": 1.0, "right": 0.5 }, "volume": 150.0 })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_user_voice_settings_from_voice_state() -> Result<()> {
+    let mut client = MockIpc::new(|request| match request["cmd"].as_str() {
+        Some("GET_CHANNEL") => vec![reply(
+            request,
+            json!({
+                "id": "199737254929760257", "guild_id": "199737254929760256", "name": "General", "type": 2,
+                "voice_states": [{
+                    "voice_state": { "mute": false, "deaf": false, "self_mute": true, "self_deaf": false, "suppress": false },
+                    "user": user("53908232506183680", "mason"),
+                    "nick": "Mason", "volume": 110.0, "mute": true, "pan": { "left": 1.0, "right": 1.0 }
+                }]
+            }),
+        )],
+        _ => vec![],
+    });
+    client.connect()?;
+
+    let channel = client.get_channel(Snowflake::new(199737254929760257))?;
+    let state = &channel.voice_states[0];
+    assert!(state.voice_state.self_mute);
+    assert_eq!(
+        state.user_voice_settings(),
+        UserVoiceSettings::new(Snowflake::new(53908232506183680)).volume(110.0).mute(true).pan(Pan::default())
+    );
+
+    Ok(())
+}
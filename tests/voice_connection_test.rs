@@ -0,0 +1,120 @@
+mod common;
+
+use common::{event, reply, MockIpc};
+use discord_ipc_rp::{
+    models::{VoiceConnectionState, VoiceConnectionStatus},
+    voice::{VoiceConnectionMonitor, VoiceConnectionTransition, VoiceRoster},
+    DiscordIpc, Result,
+};
+use serde_json::{json, Value};
+
+fn status(state: &str, pings: serde_json::Value, last_ping: f64) -> VoiceConnectionStatus {
+    serde_json::from_value(json!({
+        "state": state, "hostname": "eu-west.discord.gg", "pings": pings, "average_ping": null, "last_ping": last_ping
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_monitor_transitions() -> Result<()> {
+    let mut client = MockIpc::with_subscriptions(|_| vec![]);
+    client.connect()?;
+
+    let mut monitor = VoiceConnectionMonitor::subscribe(&mut client, 10)?;
+    assert_eq!(client.commands()[0]["evt"], "VOICE_CONNECTION_STATUS");
+
+    client.push(1, event("VOICE_CONNECTION_STATUS", json!({ "state": "VOICE_CONNECTING", "hostname": null, "pings": [] })));
+    client.push(1, event("VOICE_CONNECTION_STATUS", json!({ "state": "VOICE_CONNECTED", "hostname": "eu-west.discord.gg", "pings": [] })));
+    client.push(1, event("VOICE_CONNECTION_STATUS", json!({ "state": "VOICE_CONNECTED", "hostname": "eu-west.discord.gg", "pings": [] })));
+
+    let transitions: Vec<_> = (0..3).map(|_| Ok(monitor.handle_event(&client.recv_event()?))).collect::<Result<_>>()?;
+    assert_eq!(
+        transitions,
+        [
+            Some(VoiceConnectionTransition { from: None, to: VoiceConnectionState::VoiceConnecting }),
+            Some(VoiceConnectionTransition {
+                from: Some(VoiceConnectionState::VoiceConnecting),
+                to: VoiceConnectionState::VoiceConnected
+            }),
+            None,
+        ]
+    );
+    assert_eq!(monitor.hostname(), Some("eu-west.discord.gg"));
+    assert!(monitor.latency().is_none());
+
+    monitor.unsubscribe(&mut client)?;
+    assert_eq!(client.commands()[1]["cmd"], "UNSUBSCRIBE");
+
+    // a monitor that did not subscribe does not unsubscribe either
+    VoiceConnectionMonitor::new(10).unsubscribe(&mut client)?;
+    assert_eq!(client.commands().len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_monitor_latency_with_timed_pings() {
+    let mut monitor = VoiceConnectionMonitor::new(3);
+
+    monitor.update(&status("VOICE_CONNECTED", json!([{ "time": 1, "value": 40 }, { "time": 2, "value": 60 }]), 60.0));
+    monitor.update(&status(
+        "VOICE_CONNECTED",
+        json!([{ "time": 1, "value": 40 }, { "time": 2, "value": 60 }, { "time": 3, "value": 20 }, { "time": 4, "value": 100 }]),
+        100.0,
+    ));
+
+    let latency = monitor.latency().unwrap();
+    assert_eq!(latency.pings, [60.0, 20.0, 100.0]);
+    assert_eq!((latency.min, latency.avg, latency.max, latency.last()), (20.0, 60.0, 100.0, 100.0));
+}
+
+#[test]
+fn test_monitor_latency_with_untimed_pings() {
+    let mut monitor = VoiceConnectionMonitor::new(2);
+
+    monitor.update(&status("VOICE_CONNECTED", json!([30, 50]), 50.0));
+    monitor.update(&status("VOICE_CONNECTED", json!([30, 50, 70]), 70.0));
+    monitor.update(&status("VOICE_CONNECTED", json!([30, 50, 70, 90]), 90.0));
+
+    let latency = monitor.latency().unwrap();
+    assert_eq!(latency.pings, [70.0, 90.0]);
+    assert_eq!(latency.avg, 80.0);
+}
+
+#[test]
+fn test_monitor_and_roster_share_subscription() -> Result<()> {
+    let mut client = MockIpc::with_subscriptions(|request| vec![reply(request, Value::Null)]);
+    client.connect()?;
+
+    let monitor = VoiceConnectionMonitor::subscribe(&mut client, 10)?;
+    let roster = VoiceRoster::subscribe(&mut client)?;
+    let unsubscribed = |client: &MockIpc| {
+        client
+            .commands()
+            .iter()
+            .filter(|command| command["cmd"] == "UNSUBSCRIBE" && command["evt"] == "VOICE_CONNECTION_STATUS")
+            .count()
+    };
+
+    // the roster still depends on the voice connection events
+    monitor.unsubscribe(&mut client)?;
+    assert_eq!(unsubscribed(&client), 0);
+
+    roster.unsubscribe(&mut client)?;
+    assert_eq!(unsubscribed(&client), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_monitor_unknown_state() -> Result<()> {
+    let mut client = MockIpc::with_subscriptions(|_| vec![]);
+    client.connect()?;
+    let mut monitor = VoiceConnectionMonitor::subscribe(&mut client, 10)?;
+
+    client.push(1, event("VOICE_CONNECTION_STATUS", json!({ "state": "VOICE_RECONNECTING", "hostname": null, "pings": [] })));
+    let transition = monitor.handle_event(&client.recv_event()?);
+    assert_eq!(transition, Some(VoiceConnectionTransition { from: None, to: VoiceConnectionState::Unknown }));
+
+    Ok(())
+}
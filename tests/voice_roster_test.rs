@@ -0,0 +1,117 @@
+mod common;
+
+use common::{event, reply, user, MockIpc};
+use discord_ipc_rp::{
+    events::Event,
+    models::{Snowflake, VoiceConnectionState},
+    voice::VoiceRoster,
+    DiscordIpc, Result,
+};
+use serde_json::{json, Value};
+
+const TEAM: &str = "199737254929760257";
+const LOBBY: &str = "199737254929760258";
+
+fn voice_state(id: &str, username: &str, self_mute: bool) -> Value {
+    json!({
+        "voice_state": { "mute": false, "deaf": false, "self_mute": self_mute, "self_deaf": false, "suppress": false },
+        "user": user(id, username), "nick": null, "volume": 100.0, "mute": false, "pan": { "left": 1.0, "right": 1.0 }
+    })
+}
+
+fn channel(id: &str, voice_states: Vec<Value>) -> Value {
+    json!({ "id": id, "guild_id": "199737254929760256", "name": "Voice", "type": 2, "voice_states": voice_states })
+}
+
+fn client() -> MockIpc {
+    MockIpc::with_subscriptions(|request| match request["cmd"].as_str() {
+        Some("GET_SELECTED_VOICE_CHANNEL") => vec![reply(request, channel(TEAM, vec![voice_state("1", "alice", false)]))],
+        Some("GET_CHANNEL") => vec![reply(request, channel(LOBBY, vec![voice_state("3", "carol", true)]))],
+        _ => vec![],
+    })
+}
+
+fn next(client: &mut MockIpc, roster: &mut VoiceRoster, evt: &str, data: Value) -> Result<bool> {
+    client.push(1, event(evt, data));
+    let event = client.recv_event()?;
+    roster.handle_event(client, &event)
+}
+
+fn subscriptions(client: &MockIpc, cmd: &str) -> Vec<(String, Value)> {
+    client
+        .commands()
+        .iter()
+        .filter(|command| command["cmd"] == cmd)
+        .map(|command| (command["evt"].as_str().unwrap().to_string(), command["args"]["channel_id"].clone()))
+        .collect()
+}
+
+#[test]
+fn test_roster_tracks_members() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    let mut roster = VoiceRoster::subscribe(&mut client)?;
+    assert_eq!(roster.channel_id(), Some(TEAM.parse()?));
+    assert_eq!(roster.members().count(), 1);
+    assert!(subscriptions(&client, "SUBSCRIBE").contains(&("SPEAKING_START".to_string(), json!(TEAM))));
+
+    assert!(next(&mut client, &mut roster, "VOICE_STATE_CREATE", voice_state("2", "bob", false))?);
+    assert!(next(&mut client, &mut roster, "SPEAKING_START", json!({ "user_id": "2" }))?);
+    assert!(!next(&mut client, &mut roster, "SPEAKING_START", json!({ "user_id": "2" }))?);
+    assert!(roster.is_speaking(Snowflake::new(2)));
+
+    assert!(next(&mut client, &mut roster, "VOICE_STATE_UPDATE", voice_state("1", "alice", true))?);
+    assert!(roster.is_muted(Snowflake::new(1)));
+    assert!(!roster.is_deafened(Snowflake::new(1)));
+
+    let names: Vec<_> = roster.members().map(|member| member.user.username.as_str()).collect();
+    assert_eq!(names, ["alice", "bob"]);
+
+    assert!(next(&mut client, &mut roster, "VOICE_STATE_DELETE", voice_state("2", "bob", false))?);
+    assert!(roster.member(Snowflake::new(2)).is_none());
+    assert_eq!(roster.speaking().count(), 0);
+
+    assert!(next(&mut client, &mut roster, "VOICE_CONNECTION_STATUS", json!({ "state": "VOICE_CONNECTED", "pings": [] }))?);
+    assert_eq!(roster.connection_state(), Some(VoiceConnectionState::VoiceConnected));
+
+    Ok(())
+}
+
+#[test]
+fn test_roster_follows_channel() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+    let mut roster = VoiceRoster::subscribe(&mut client)?;
+
+    assert!(next(&mut client, &mut roster, "VOICE_CHANNEL_SELECT", json!({ "channel_id": LOBBY, "guild_id": "199737254929760256" }))?);
+    assert_eq!(roster.channel_id(), Some(LOBBY.parse()?));
+    assert!(roster.is_muted(Snowflake::new(3)));
+    assert!(subscriptions(&client, "UNSUBSCRIBE").contains(&("VOICE_STATE_CREATE".to_string(), json!(TEAM))));
+    assert!(subscriptions(&client, "SUBSCRIBE").contains(&("VOICE_STATE_CREATE".to_string(), json!(LOBBY))));
+
+    assert!(next(&mut client, &mut roster, "VOICE_CHANNEL_SELECT", json!({ "channel_id": null, "guild_id": null }))?);
+    assert_eq!(roster.channel_id(), None);
+    assert_eq!(roster.members().count(), 0);
+
+    // events of other channels are ignored once the user left
+    assert!(!next(&mut client, &mut roster, "VOICE_STATE_CREATE", voice_state("2", "bob", false))?);
+
+    Ok(())
+}
+
+#[test]
+fn test_recv_event_returns_queued_events_first() -> Result<()> {
+    let mut client = MockIpc::new(|request| match request["cmd"].as_str() {
+        Some("GET_SELECTED_VOICE_CHANNEL") => vec![event("SPEAKING_STOP", json!({ "user_id": "1" })), reply(request, Value::Null)],
+        _ => vec![],
+    });
+    client.connect()?;
+    assert_eq!(client.get_selected_voice_channel()?, None);
+
+    client.push(1, event("SPEAKING_START", json!({ "user_id": "2" })));
+    assert!(matches!(client.recv_event()?, Event::SpeakingStop(speaking) if speaking.user_id == Snowflake::new(1)));
+    assert!(matches!(client.recv_event()?, Event::SpeakingStart(speaking) if speaking.user_id == Snowflake::new(2)));
+
+    Ok(())
+}
@@ -0,0 +1,147 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use common::{event, reply, MockIpc};
+use discord_ipc_rp::{
+    events::Event,
+    models::{KeyType, VoiceMode},
+    DiscordIpc, Result,
+};
+use serde_json::{json, Value};
+
+fn settings() -> Value {
+    json!({
+        "input": { "available_devices": [{ "id": "default", "name": "Default" }], "device_id": "default", "volume": 49.5 },
+        "output": { "available_devices": [{ "id": "default", "name": "Default" }], "device_id": "default", "volume": 100.0 },
+        "mode": {
+            "type": "PUSH_TO_TALK", "auto_threshold": true, "threshold": -46.9, "delay": 98.0,
+            "shortcut": [{ "type": 2, "code": 162, "name": "ctrl" }, { "type": 0, "code": 86, "name": "v" }]
+        },
+        "automatic_gain_control": true, "echo_cancellation": true, "noise_suppression": true,
+        "qos": false, "silence_warning": true, "deaf": false, "mute": false
+    })
+}
+
+/// A client whose voice settings are updated by SET_VOICE_SETTINGS.
+fn client() -> MockIpc {
+    client_with(Arc::new(Mutex::new(settings())))
+}
+
+/// A client whose voice settings are `current`, so that the test can change them.
+fn client_with(current: Arc<Mutex<Value>>) -> MockIpc {
+    MockIpc::new(move |request| {
+        let mut current = current.lock().unwrap();
+        match request["cmd"].as_str() {
+            Some("GET_VOICE_SETTINGS") => vec![reply(request, current.clone())],
+            Some("SET_VOICE_SETTINGS") => {
+                for (key, value) in request["args"].as_object().unwrap() {
+                    match value {
+                        Value::Object(fields) => fields.iter().for_each(|(field, value)| current[key][field] = value.clone()),
+                        _ => current[key] = value.clone(),
+                    }
+                }
+                vec![reply(request, current.clone())]
+            }
+            _ => vec![],
+        }
+    })
+}
+
+#[test]
+fn test_get_voice_settings() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    let settings = client.get_voice_settings()?;
+    assert_eq!(settings.input.volume, 49.5);
+    assert_eq!(settings.input.available_devices[0].name, "Default");
+    assert_eq!(settings.mode.kind, VoiceMode::PushToTalk);
+    assert_eq!(settings.mode.shortcut[0].kind, KeyType::KeyboardModifierKey);
+    assert_eq!(settings.mode.shortcut[1].name, "v");
+    assert!(!settings.mute);
+
+    Ok(())
+}
+
+#[test]
+fn test_set_voice_settings_sends_changes() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    let mut settings = client.get_voice_settings()?;
+    settings.mute = true;
+    settings.output.volume = 150.0;
+    let updated = client.set_voice_settings(&settings)?;
+
+    assert!(updated.mute);
+    assert_eq!(updated, settings);
+    assert_eq!(client.commands()[1]["cmd"], "GET_VOICE_SETTINGS");
+    assert_eq!(client.commands()[2]["args"], json!({ "mute": true, "output": { "volume": 150.0 } }));
+
+    settings.deaf = true;
+    client.set_voice_settings(&settings)?;
+    assert_eq!(client.commands()[4]["args"], json!({ "deaf": true }));
+
+    Ok(())
+}
+
+#[test]
+fn test_set_voice_settings_fetches_current() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    let mut settings: discord_ipc_rp::models::VoiceSettings = serde_json::from_value(settings())?;
+    settings.mode.kind = VoiceMode::VoiceActivity;
+    client.set_voice_settings(&settings)?;
+
+    let commands = client.commands();
+    assert_eq!(commands[0]["cmd"], "GET_VOICE_SETTINGS");
+    assert_eq!(commands[1]["args"], json!({ "mode": { "type": "VOICE_ACTIVITY" } }));
+
+    Ok(())
+}
+
+#[test]
+fn test_set_voice_settings_changed_elsewhere() -> Result<()> {
+    let current = Arc::new(Mutex::new(settings()));
+    let mut client = client_with(current.clone());
+    client.connect()?;
+    let mut settings = client.get_voice_settings()?;
+
+    // the user mutes themselves in the Discord client, without any update event
+    current.lock().unwrap()["mute"] = json!(true);
+
+    settings.mute = false;
+    let updated = client.set_voice_settings(&settings)?;
+    assert_eq!(client.commands()[2]["args"], json!({ "mute": false }));
+    assert!(!updated.mute);
+
+    Ok(())
+}
+
+#[test]
+fn test_voice_settings_update_event() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    let mut muted = settings();
+    muted["mute"] = json!(true);
+    client.push(1, event("VOICE_SETTINGS_UPDATE", muted));
+    let Event::VoiceSettingsUpdate(settings) = client.recv_event()? else { panic!("expected a voice settings update") };
+    assert!(settings.mute);
+
+    Ok(())
+}
+
+#[test]
+fn test_set_unchanged_voice_settings() -> Result<()> {
+    let mut client = client();
+    client.connect()?;
+
+    let settings = client.get_voice_settings()?;
+    assert_eq!(client.set_voice_settings(&settings)?, settings);
+    assert!(client.commands().iter().all(|command| command["cmd"] == "GET_VOICE_SETTINGS"));
+
+    Ok(())
+}